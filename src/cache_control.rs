@@ -0,0 +1,107 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header::CACHE_CONTROL, HeaderValue, Method},
+    middleware::Next,
+    response::Response,
+};
+
+#[derive(Clone, Default)]
+pub struct RouteCacheMaxAge {
+    routes: Arc<HashMap<String, u64>>,
+}
+
+impl RouteCacheMaxAge {
+    pub fn with_route(mut self, path: &str, seconds: u64) -> Self {
+        Arc::make_mut(&mut self.routes).insert(path.to_owned(), seconds);
+        self
+    }
+
+    fn header(&self, path: Option<&MatchedPath>) -> Option<HeaderValue> {
+        let seconds = self.routes.get(path?.as_str())?;
+
+        Some(match seconds {
+            0 => HeaderValue::from_static("no-store"),
+            seconds => HeaderValue::from_str(&format!("max-age={seconds}")).ok()?,
+        })
+    }
+}
+
+pub async fn cache_control_middleware(
+    State(max_age): State<RouteCacheMaxAge>,
+    path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let header = (request.method() == Method::GET)
+        .then(|| max_age.header(path.as_ref()))
+        .flatten();
+    let mut response = next.run(request).await;
+
+    if let Some(header) = header {
+        if response.status().is_success() && !response.headers().contains_key(CACHE_CONTROL) {
+            response.headers_mut().insert(CACHE_CONTROL, header);
+        }
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::StatusCode,
+        middleware::from_fn_with_state,
+        routing::get,
+        Router,
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn router(max_age: RouteCacheMaxAge) -> Router {
+        Router::new()
+            .nest(
+                "/api",
+                Router::new()
+                    .route("/today", get(|| async { StatusCode::OK }))
+                    .route("/random", get(|| async { StatusCode::OK }))
+                    .route("/{id}", get(|| async { StatusCode::NOT_FOUND }))
+                    .route("/latest", get(|| async { StatusCode::OK })),
+            )
+            .layer(from_fn_with_state(max_age, cache_control_middleware))
+    }
+
+    async fn cache_control(router: Router, uri: &str) -> Option<HeaderValue> {
+        router
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .headers()
+            .get(CACHE_CONTROL)
+            .cloned()
+    }
+
+    #[tokio::test]
+    async fn per_route_max_age() {
+        let router = router(
+            RouteCacheMaxAge::default()
+                .with_route("/api/today", 86400)
+                .with_route("/api/random", 0)
+                .with_route("/api/{id}", 60),
+        );
+
+        assert_eq!(
+            cache_control(router.clone(), "/api/today").await.unwrap(),
+            "max-age=86400"
+        );
+        assert_eq!(
+            cache_control(router.clone(), "/api/random").await.unwrap(),
+            "no-store"
+        );
+        assert_eq!(cache_control(router.clone(), "/api/1").await, None);
+        assert_eq!(cache_control(router, "/api/latest").await, None);
+    }
+}
@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::{from_fn_with_state, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use rand::Rng;
+use tokio::time::sleep;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Chaos {
+    pub delay: Duration,
+    pub error_rate: f64,
+}
+
+pub fn inject_chaos(router: Router, chaos: Chaos) -> Router {
+    router.layer(from_fn_with_state(chaos, chaos_middleware))
+}
+
+async fn chaos_middleware(State(chaos): State<Chaos>, request: Request, next: Next) -> Response {
+    if !chaos.delay.is_zero() {
+        sleep(chaos.delay).await;
+    }
+
+    if rand::rng().random_bool(chaos.error_rate) {
+        return (StatusCode::INTERNAL_SERVER_ERROR, "Injected chaos error").into_response();
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, routing::get};
+    use tokio::time::Instant;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn router() -> Router {
+        Router::new().route("/health", get(|| async { StatusCode::OK }))
+    }
+
+    async fn status(router: Router) -> StatusCode {
+        router
+            .oneshot(Request::get("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn full_error_rate() {
+        let router = inject_chaos(
+            router(),
+            Chaos {
+                error_rate: 1.0,
+                ..Default::default()
+            },
+        );
+
+        for _ in 0..20 {
+            assert_eq!(
+                status(router.clone()).await,
+                StatusCode::INTERNAL_SERVER_ERROR
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn no_errors_by_default() {
+        let router = inject_chaos(router(), Chaos::default());
+
+        for _ in 0..20 {
+            assert_eq!(status(router.clone()).await, StatusCode::OK);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn delay() {
+        let router = inject_chaos(
+            router(),
+            Chaos {
+                delay: Duration::from_millis(500),
+                ..Default::default()
+            },
+        );
+        let started = Instant::now();
+
+        assert_eq!(status(router).await, StatusCode::OK);
+        assert!(started.elapsed() >= Duration::from_millis(500));
+    }
+}
@@ -1,8 +1,12 @@
-#![allow(clippy::struct_field_names)]
-use std::net::{IpAddr, Ipv4Addr};
+#![allow(clippy::struct_field_names, clippy::struct_excessive_bools)]
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    path::PathBuf,
+};
 
-use clap::{value_parser, Args, Parser, ValueEnum};
-use tracing::Level;
+use clap::{value_parser, ArgAction, Args, Parser, ValueEnum};
+use tracing::{level_filters::LevelFilter, Level};
+use tracing_subscriber::EnvFilter;
 
 #[derive(Parser, Debug)]
 #[clap(version, about)]
@@ -14,7 +18,11 @@ pub struct Config {
     #[command(flatten)]
     pub storage: Storage,
     #[command(flatten)]
+    pub endpoints: Endpoints,
+    #[command(flatten)]
     pub authentication: Authentication,
+    #[command(flatten)]
+    pub chaos: ChaosTesting,
 }
 
 #[derive(Args, Clone, Debug)]
@@ -23,14 +31,159 @@ pub struct Runtime {
     pub bind_host: IpAddr,
     #[arg(long, env = "PORT", value_parser = value_parser!(u16).range(1..), default_value = "8080")]
     pub bind_port: u16,
+    #[cfg(feature = "grpc")]
+    #[arg(long, env, value_parser = value_parser!(u16).range(1..))]
+    pub grpc_port: Option<u16>,
+    #[arg(long, env, default_value = "http://localhost:8080")]
+    pub public_url: String,
+    #[arg(long, env)]
+    pub http2: bool,
+    #[arg(long, env, value_parser = value_parser!(u32).range(8192..))]
+    pub max_header_bytes: Option<u32>,
+    #[arg(long, env)]
+    pub header_read_timeout_ms: Option<u64>,
+    #[arg(long, env)]
+    pub server_timing: bool,
+    #[arg(long, env)]
+    pub disable_random: bool,
+    #[arg(long, env)]
+    pub auto_tag_rules: Option<PathBuf>,
+    #[arg(long, env)]
+    pub profanity_words: Option<PathBuf>,
+    #[arg(long, env, default_value_t, value_enum, requires = "profanity_words")]
+    pub profanity_mode: ProfanityPolicy,
+    #[arg(long, env, default_value = "30000")]
+    pub request_timeout_ms: u64,
+    #[arg(long, env, value_delimiter = ',', value_parser = parse_route_timeout)]
+    pub route_timeout_ms: Vec<(String, u64)>,
+    #[arg(long, env, value_delimiter = ',', value_parser = parse_route_max_age)]
+    pub route_cache_max_age: Vec<(String, u64)>,
+    #[arg(long, env, default_value_t, value_enum)]
+    pub trailing_slash: TrailingSlashPolicy,
+    #[arg(long, env, value_enum)]
+    pub require_https: Option<HttpsPolicy>,
+    #[arg(long, env, value_delimiter = ',', requires = "require_https")]
+    pub require_https_exempt: Vec<String>,
+    #[arg(long, env, value_parser = value_parser!(u32).range(1..), default_value = "50")]
+    pub max_search_results: u32,
+    #[arg(long, env, default_value = "1")]
+    pub min_body_length: usize,
+    #[arg(long, env, default_value_t, value_enum)]
+    pub body_control_characters: ControlCharactersPolicy,
+    #[arg(long, env)]
+    pub expose_backend_header: bool,
+    #[arg(long, env)]
+    pub expose_actors: bool,
+    #[arg(long, env)]
+    pub self_test: bool,
+}
+
+#[derive(Clone, ValueEnum, Default, Debug)]
+pub enum ControlCharactersPolicy {
+    #[default]
+    Reject,
+    Strip,
+}
+
+#[derive(Clone, ValueEnum, Default, Debug)]
+pub enum ProfanityPolicy {
+    #[default]
+    Reject,
+    Mask,
+}
+
+#[derive(Clone, ValueEnum, Default, Debug)]
+pub enum TrailingSlashPolicy {
+    #[default]
+    Strict,
+    Trim,
+    Redirect,
+}
+
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum HttpsPolicy {
+    Redirect,
+    Reject,
+}
+
+fn parse_route_timeout(raw: &str) -> Result<(String, u64), String> {
+    let (path, timeout) = raw
+        .rsplit_once('=')
+        .ok_or(format!("Expected `<path>=<milliseconds>`, got {raw:?}"))?;
+    let timeout = timeout
+        .parse()
+        .map_err(|err| format!("Invalid timeout for {path:?}: {err}"))?;
+
+    Ok((path.to_owned(), timeout))
+}
+
+fn parse_route_max_age(raw: &str) -> Result<(String, u64), String> {
+    let (path, max_age) = raw
+        .rsplit_once('=')
+        .ok_or(format!("Expected `<path>=<seconds>`, got {raw:?}"))?;
+    let max_age = max_age
+        .parse()
+        .map_err(|err| format!("Invalid max-age for {path:?}: {err}"))?;
+
+    Ok((path.to_owned(), max_age))
+}
+
+fn parse_error_rate(raw: &str) -> Result<f64, String> {
+    let rate = raw
+        .parse::<f64>()
+        .map_err(|err| format!("Invalid error rate {raw:?}: {err}"))?;
+
+    if (0.0..=1.0).contains(&rate) {
+        Ok(rate)
+    } else {
+        Err(format!("Error rate must be between 0 and 1, got {rate}"))
+    }
+}
+
+fn parse_log_level(raw: &str) -> Result<Level, String> {
+    [
+        Level::TRACE,
+        Level::DEBUG,
+        Level::INFO,
+        Level::WARN,
+        Level::ERROR,
+    ]
+    .into_iter()
+    .find(|level| level.as_str().eq_ignore_ascii_case(raw.trim()))
+    .ok_or_else(|| {
+        format!("Invalid log level {raw:?}, expected one of: trace, debug, info, warn, error")
+    })
+}
+
+fn parse_log_filter(raw: &str) -> Result<String, String> {
+    EnvFilter::builder()
+        .parse(raw)
+        .map_err(|err| format!("Invalid log filter {raw:?}: {err}"))?;
+
+    Ok(raw.to_owned())
 }
 
 #[derive(Args, Clone, Debug)]
 pub struct Logging {
-    #[arg(long, env, default_value = "INFO")]
+    #[arg(long, env, default_value = "INFO", value_parser = parse_log_level)]
     pub log_level: Level,
+    #[arg(long, env, value_parser = parse_log_filter)]
+    pub log_filter: Option<String>,
     #[arg(long, env, default_value_t, value_enum)]
     pub log_format: LogFormat,
+    #[arg(long, env)]
+    pub log_writes: bool,
+    #[cfg(feature = "sentry")]
+    #[arg(long, env)]
+    pub sentry_dsn: Option<sentry::types::Dsn>,
+}
+
+impl Logging {
+    pub fn filter(&self) -> EnvFilter {
+        EnvFilter::builder()
+            .parse_lossy(self.log_filter.as_deref().unwrap_or_default())
+            .add_directive(LevelFilter::from_level(self.log_level).into())
+    }
 }
 
 #[derive(Clone, ValueEnum, Default, Debug)]
@@ -46,6 +199,25 @@ pub enum StorageType {
     Mocked,
     #[default]
     Sqlx,
+    JsonFile,
+}
+
+impl StorageType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Mocked => "mocked",
+            Self::Sqlx => "sqlx",
+            Self::JsonFile => "json-file",
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum, Default, Debug)]
+pub enum RandomStrategyType {
+    #[default]
+    OrderByRandom,
+    MinMaxId,
+    Weighted,
 }
 
 #[derive(Args, Clone, Debug)]
@@ -54,6 +226,45 @@ pub struct Storage {
     pub storage_type: StorageType,
     #[arg(long, env, default_value = String::new(), value_enum)]
     pub storage_dsn: String,
+    #[arg(long, env, default_value = "30000")]
+    pub storage_acquire_timeout_ms: u64,
+    #[arg(long, env, alias = "storage-statement-timeout-ms")]
+    pub statement_timeout_ms: Option<u64>,
+    #[arg(long, env, default_value_t, value_enum)]
+    pub storage_random_strategy: RandomStrategyType,
+    #[arg(long, env)]
+    pub storage_change_stream: bool,
+    /// Skip change notifications for writes made by this instance. An
+    /// instance running --storage-change-stream only sees writes from
+    /// instances that still notify, so enable this only when none does.
+    #[arg(long, env, conflicts_with = "storage_change_stream")]
+    pub storage_suppress_change_notifications: bool,
+    #[arg(long, env, value_parser = value_parser!(u32).range(1..))]
+    pub storage_breaker_threshold: Option<u32>,
+    #[arg(long, env, default_value = "30000")]
+    pub storage_breaker_cooldown_ms: u64,
+    #[arg(long, env)]
+    pub idempotent_delete: bool,
+    #[arg(long, env)]
+    pub reject_duplicate_bodies: bool,
+    #[arg(long, env, value_parser = value_parser!(u64).range(1..))]
+    pub max_total_facts: Option<u64>,
+    #[arg(long, env, value_parser = value_parser!(i32).range(1..))]
+    pub storage_id_start: Option<i32>,
+    #[arg(long, env, default_value = "facts.json")]
+    pub storage_file: PathBuf,
+    #[arg(long, env, default_value = "500")]
+    pub storage_file_debounce_ms: u64,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct Endpoints {
+    #[arg(long, env, default_value_t = true, action = ArgAction::Set)]
+    pub enable_create: bool,
+    #[arg(long, env, default_value_t = true, action = ArgAction::Set)]
+    pub enable_delete: bool,
+    #[arg(long, env, default_value_t = true, action = ArgAction::Set)]
+    pub enable_search: bool,
 }
 
 #[derive(Args, Clone, Debug)]
@@ -61,3 +272,172 @@ pub struct Authentication {
     #[arg(long, env)]
     pub password_hash: String,
 }
+
+#[derive(Args, Clone, Debug)]
+pub struct ChaosTesting {
+    #[arg(long, env, hide = true)]
+    pub chaos_enable: bool,
+    #[arg(long, env, hide = true, requires = "chaos_enable", default_value = "0")]
+    pub chaos_delay_ms: u64,
+    #[arg(
+        long,
+        env,
+        hide = true,
+        requires = "chaos_enable",
+        value_parser = parse_error_rate,
+        default_value = "0"
+    )]
+    pub chaos_error_rate: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::{error, info, subscriber::with_default};
+    use tracing_subscriber::{fmt, layer::SubscriberExt, registry};
+
+    use super::*;
+    use crate::test_support::CapturedLogs;
+
+    fn logging(args: &[&str]) -> Logging {
+        Config::try_parse_from([&["api", "--password-hash", ""], args].concat())
+            .unwrap()
+            .logging
+    }
+
+    #[test]
+    fn log_filter_silences_target() {
+        let logs = CapturedLogs::default();
+        let subscriber = registry()
+            .with(logging(&["--log-filter", "noisy=off"]).filter())
+            .with(fmt::layer().with_ansi(false).with_writer(logs.clone()));
+
+        with_default(subscriber, || {
+            info!(target: "quiet", "kept");
+            error!(target: "noisy", "dropped");
+        });
+
+        let logs = logs.contents();
+        assert!(logs.contains("kept"));
+        assert!(!logs.contains("dropped"));
+    }
+
+    #[test]
+    fn lowercase_log_level() {
+        assert_eq!(logging(&["--log-level", "debug"]).log_level, Level::DEBUG);
+        assert_eq!(logging(&["--log-level", "Warn"]).log_level, Level::WARN);
+    }
+
+    #[test]
+    fn invalid_log_level() {
+        let err =
+            Config::try_parse_from(["api", "--password-hash", "", "--log-level", "INFORMATION"])
+                .unwrap_err()
+                .to_string();
+
+        assert!(err.contains(r#"Invalid log level "INFORMATION""#), "{err}");
+        assert!(err.contains("trace, debug, info, warn, error"), "{err}");
+    }
+
+    #[test]
+    fn endpoints_enabled_by_default() {
+        let endpoints =
+            Config::try_parse_from(["api", "--password-hash", "", "--enable-delete", "false"])
+                .unwrap()
+                .endpoints;
+
+        assert!(endpoints.enable_create);
+        assert!(!endpoints.enable_delete);
+        assert!(endpoints.enable_search);
+    }
+
+    #[test]
+    fn statement_timeout_flag_and_alias() {
+        for flag in ["--statement-timeout-ms", "--storage-statement-timeout-ms"] {
+            let storage = Config::try_parse_from(["api", "--password-hash", "", flag, "500"])
+                .unwrap()
+                .storage;
+
+            assert_eq!(storage.statement_timeout_ms, Some(500), "{flag}");
+        }
+    }
+
+    #[test]
+    fn change_notifications_suppression_is_opt_in() {
+        let storage = Config::try_parse_from(["api", "--password-hash", ""])
+            .unwrap()
+            .storage;
+
+        assert!(!storage.storage_suppress_change_notifications);
+        assert!(Config::try_parse_from([
+            "api",
+            "--password-hash",
+            "",
+            "--storage-change-stream",
+            "--storage-suppress-change-notifications",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn chaos_requires_enable() {
+        assert!(
+            Config::try_parse_from(["api", "--password-hash", "", "--chaos-error-rate", "1"])
+                .is_err()
+        );
+
+        let chaos = Config::try_parse_from([
+            "api",
+            "--password-hash",
+            "",
+            "--chaos-enable",
+            "--chaos-error-rate",
+            "1",
+        ])
+        .unwrap()
+        .chaos;
+
+        assert!(chaos.chaos_enable);
+        assert!((chaos.chaos_error_rate - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn route_cache_max_age() {
+        let runtime = Config::try_parse_from([
+            "api",
+            "--password-hash",
+            "",
+            "--route-cache-max-age",
+            "/api/facts/this-week=86400,/api/facts/random=0",
+        ])
+        .unwrap()
+        .runtime;
+
+        assert_eq!(
+            runtime.route_cache_max_age,
+            [
+                ("/api/facts/this-week".to_owned(), 86400),
+                ("/api/facts/random".to_owned(), 0),
+            ]
+        );
+        assert!(Config::try_parse_from([
+            "api",
+            "--password-hash",
+            "",
+            "--route-cache-max-age",
+            "/api/facts/random",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn invalid_log_filter() {
+        assert!(Config::try_parse_from([
+            "api",
+            "--password-hash",
+            "",
+            "--log-filter",
+            "noisy=loud",
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use axum::{
+    http::{HeaderName, HeaderValue},
+    Router,
+};
+use tower_http::set_header::SetResponseHeaderLayer;
+
+const DATA_SOURCE_HEADER: HeaderName = HeaderName::from_static("x-data-source");
+
+pub fn expose_data_source(router: Router, source: &'static str) -> Router {
+    router.layer(SetResponseHeaderLayer::overriding(
+        DATA_SOURCE_HEADER,
+        HeaderValue::from_static(source),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, extract::Request, http::StatusCode, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn router() -> Router {
+        Router::new().route("/health", get(|| async { StatusCode::OK }))
+    }
+
+    #[tokio::test]
+    async fn header_reflects_backend() {
+        for source in ["mocked", "sqlx", "json-file"] {
+            let response = expose_data_source(router(), source)
+                .oneshot(Request::get("/health").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+
+            assert_eq!(response.headers()[DATA_SOURCE_HEADER], source);
+        }
+    }
+
+    #[tokio::test]
+    async fn hidden_by_default() {
+        let response = router()
+            .oneshot(Request::get("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert!(!response.headers().contains_key(DATA_SOURCE_HEADER));
+    }
+}
@@ -0,0 +1,46 @@
+use sentry::{integrations::tracing::SentryLayer, types::Dsn, ClientInitGuard, ClientOptions};
+use tracing::Subscriber;
+use tracing_subscriber::registry::LookupSpan;
+
+pub fn init(dsn: Option<Dsn>) -> Option<ClientInitGuard> {
+    let mut options = ClientOptions::new();
+    options.dsn = Some(dsn?);
+    options.release = sentry::release_name!();
+
+    Some(sentry::init(options))
+}
+
+pub fn layer<S>() -> SentryLayer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    sentry::integrations::tracing::layer()
+}
+
+#[cfg(test)]
+mod tests {
+    use sentry::{test::with_captured_events, Level};
+    use tracing::{error, subscriber::set_default, warn};
+    use tracing_subscriber::{layer::SubscriberExt, registry};
+
+    use super::*;
+
+    #[test]
+    fn disabled_without_dsn() {
+        assert!(init(None).is_none());
+    }
+
+    #[test]
+    fn error_events_are_captured() {
+        let events = with_captured_events(|| {
+            let _subscriber = set_default(registry().with(layer()));
+
+            warn!("Something looks odd");
+            error!("Something broke");
+        });
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].level, Level::Error);
+        assert_eq!(events[0].message.as_deref(), Some("Something broke"));
+    }
+}
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS fact_tags (
+  fact_id integer NOT NULL REFERENCES facts (id) ON DELETE CASCADE,
+  tag varchar(32) NOT NULL CHECK (tag <> ''),
+  PRIMARY KEY (fact_id, tag)
+)
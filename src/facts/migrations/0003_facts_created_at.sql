@@ -0,0 +1,2 @@
+ALTER TABLE facts
+  ADD COLUMN IF NOT EXISTS created_at timestamptz NOT NULL DEFAULT now()
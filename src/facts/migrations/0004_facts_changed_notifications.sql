@@ -0,0 +1,13 @@
+CREATE OR REPLACE FUNCTION notify_facts_changed() RETURNS trigger AS $$
+BEGIN
+  PERFORM pg_notify(
+    'facts_changed',
+    json_build_object('action', lower(TG_OP), 'id', COALESCE(NEW.id, OLD.id))::text
+  );
+  RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE TRIGGER facts_changed
+AFTER INSERT OR UPDATE OR DELETE ON facts
+FOR EACH ROW EXECUTE FUNCTION notify_facts_changed()
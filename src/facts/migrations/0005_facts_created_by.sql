@@ -0,0 +1,3 @@
+ALTER TABLE facts ADD COLUMN created_by text;
+
+CREATE INDEX facts_created_by_idx ON facts (created_by);
@@ -0,0 +1,20 @@
+ALTER TABLE facts ADD COLUMN updated_at timestamptz;
+
+UPDATE facts SET updated_at = created_at;
+
+ALTER TABLE facts
+  ALTER COLUMN updated_at SET DEFAULT now(),
+  ALTER COLUMN updated_at SET NOT NULL;
+
+CREATE INDEX facts_updated_at_idx ON facts (updated_at);
+
+CREATE OR REPLACE FUNCTION touch_tagged_fact() RETURNS trigger AS $$
+BEGIN
+  UPDATE facts SET updated_at = now() WHERE id = COALESCE(NEW.fact_id, OLD.fact_id);
+  RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE TRIGGER fact_tags_changed
+AFTER INSERT OR DELETE ON fact_tags
+FOR EACH ROW EXECUTE FUNCTION touch_tagged_fact();
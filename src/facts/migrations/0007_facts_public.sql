@@ -0,0 +1 @@
+ALTER TABLE facts ADD COLUMN public boolean NOT NULL DEFAULT true;
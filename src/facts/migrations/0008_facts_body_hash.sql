@@ -0,0 +1,3 @@
+ALTER TABLE facts ADD COLUMN body_hash text GENERATED ALWAYS AS (md5(body)) STORED;
+
+CREATE INDEX facts_body_hash_idx ON facts (body_hash);
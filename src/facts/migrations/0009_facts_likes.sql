@@ -0,0 +1 @@
+ALTER TABLE facts ADD COLUMN likes bigint NOT NULL DEFAULT 0;
@@ -0,0 +1 @@
+ALTER TABLE facts ADD COLUMN updated_by text;
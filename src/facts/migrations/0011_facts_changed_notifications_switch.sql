@@ -0,0 +1,11 @@
+CREATE OR REPLACE FUNCTION notify_facts_changed() RETURNS trigger AS $$
+BEGIN
+  IF current_setting('facts.notify_changes', true) IS DISTINCT FROM 'off' THEN
+    PERFORM pg_notify(
+      'facts_changed',
+      json_build_object('action', lower(TG_OP), 'id', COALESCE(NEW.id, OLD.id))::text
+    );
+  END IF;
+  RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -1,5 +1,34 @@
-pub use repository::{FactsRepository, MockedFactsRepository, SqlxFactsRepository};
-pub use router::{AppRouter, AppState};
+pub use repository::{
+    listen_changes,
+    with_session_settings,
+    CircuitBreakerFactsRepository,
+    ControlCharacters,
+    CreateFactRequest,
+    FactBody,
+    FactId,
+    FactTitle,
+    FactsRepository,
+    JsonFileFactsRepository,
+    MinMaxId,
+    MockedFactsRepository,
+    OrderByRandom,
+    RandomStrategy,
+    SqlxFactsRepository,
+    Weighted,
+};
+#[cfg(feature = "graphql")]
+pub use router::GraphQLRouter;
+#[cfg(feature = "grpc")]
+pub use router::GrpcFacts;
+pub use router::{
+    AdminRouter,
+    AppRouter,
+    AppState,
+    AutoTagRules,
+    Endpoints,
+    ProfanityFilter,
+    ProfanityMode,
+};
 
 mod repository;
 mod router;
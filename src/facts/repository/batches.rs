@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use super::{
+    errors::ListFactsError,
+    models::{Fact, FactId},
+    FactsRepository,
+};
+
+pub struct FactBatches {
+    facts: Arc<dyn FactsRepository>,
+    size: u32,
+    after: Option<FactId>,
+    exhausted: bool,
+}
+
+impl FactBatches {
+    pub fn new(facts: Arc<dyn FactsRepository>, size: u32) -> Self {
+        Self {
+            facts,
+            size,
+            after: None,
+            exhausted: size == 0,
+        }
+    }
+
+    pub async fn next_batch(&mut self) -> Option<Result<Vec<Fact>, ListFactsError>> {
+        if self.exhausted {
+            return None;
+        }
+
+        let batch = match self.facts.list_after(self.after, self.size).await {
+            Ok(batch) => batch,
+            Err(err) => {
+                self.exhausted = true;
+                return Some(Err(err));
+            }
+        };
+
+        self.exhausted = batch.len() < self.size as usize;
+        match batch.last() {
+            Some(last) => {
+                self.after = Some(last.id());
+                Some(Ok(batch))
+            }
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{query_scalar, PgPool};
+
+    use super::*;
+    use crate::facts::SqlxFactsRepository;
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn batches_cover_table_in_order(pool: PgPool) {
+        let mut ids = Vec::new();
+        for idx in 0..7 {
+            ids.push(
+                query_scalar!(
+                    "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                    format!("Fact {idx}"),
+                    "body",
+                )
+                .fetch_one(&pool)
+                .await
+                .unwrap(),
+            );
+        }
+
+        let mut batches = FactBatches::new(Arc::new(SqlxFactsRepository::new(pool)), 3);
+        let mut sizes = Vec::new();
+        let mut scanned = Vec::new();
+        while let Some(batch) = batches.next_batch().await {
+            let batch = batch.unwrap();
+            sizes.push(batch.len());
+            scanned.extend(batch.iter().map(|fact| i32::from(fact.id())));
+        }
+
+        assert_eq!(sizes, vec![3, 3, 1]);
+        assert_eq!(scanned, ids);
+        assert!(batches.next_batch().await.is_none());
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn exact_multiple_of_batch_size(pool: PgPool) {
+        for idx in 0..4 {
+            query_scalar!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                format!("Fact {idx}"),
+                "body",
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        }
+
+        let mut batches = FactBatches::new(Arc::new(SqlxFactsRepository::new(pool)), 2);
+        let mut sizes = Vec::new();
+        while let Some(batch) = batches.next_batch().await {
+            sizes.push(batch.unwrap().len());
+        }
+
+        assert_eq!(sizes, vec![2, 2]);
+    }
+}
@@ -0,0 +1,698 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use tokio::time::Instant;
+use tracing::warn;
+
+use super::{
+    errors::{
+        AddFactTagError,
+        CreateFactError,
+        DeleteFactError,
+        GetExtremesError,
+        GetFactError,
+        GetRandomFactError,
+        ListFactsError,
+        MigrationStatusError,
+        RemoveFactTagError,
+        ResyncSequenceError,
+        UpdateFactError,
+    },
+    models::{
+        CreateFactRequest,
+        Fact,
+        FactBody,
+        FactId,
+        FactTag,
+        FactTitle,
+        FindFactsRequest,
+        InvalidFact,
+        ListFactsRequest,
+        MigrationStatus,
+        PoolStats,
+        TagMatch,
+    },
+    FactsRepository,
+};
+
+const OPEN_DETAILS: &str = "Circuit breaker is open";
+
+trait BreakerError {
+    fn is_failure(&self) -> bool;
+    fn open() -> Self;
+}
+
+impl BreakerError for GetFactError {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Unavailable { .. } | Self::UnexpectedError { .. }
+        )
+    }
+
+    fn open() -> Self {
+        Self::Unavailable {
+            inner: OPEN_DETAILS.to_owned(),
+        }
+    }
+}
+
+impl BreakerError for GetRandomFactError {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Unavailable { .. } | Self::UnexpectedError { .. }
+        )
+    }
+
+    fn open() -> Self {
+        Self::Unavailable {
+            inner: OPEN_DETAILS.to_owned(),
+        }
+    }
+}
+
+impl BreakerError for GetExtremesError {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Unavailable { .. } | Self::UnexpectedError { .. }
+        )
+    }
+
+    fn open() -> Self {
+        Self::Unavailable {
+            inner: OPEN_DETAILS.to_owned(),
+        }
+    }
+}
+
+impl BreakerError for ListFactsError {
+    fn is_failure(&self) -> bool {
+        true
+    }
+
+    fn open() -> Self {
+        Self::Unavailable {
+            inner: OPEN_DETAILS.to_owned(),
+        }
+    }
+}
+
+impl BreakerError for CreateFactError {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Unavailable { .. } | Self::UnexpectedError { .. }
+        )
+    }
+
+    fn open() -> Self {
+        Self::Unavailable {
+            inner: OPEN_DETAILS.to_owned(),
+        }
+    }
+}
+
+impl BreakerError for UpdateFactError {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Unavailable { .. } | Self::UnexpectedError { .. }
+        )
+    }
+
+    fn open() -> Self {
+        Self::Unavailable {
+            inner: OPEN_DETAILS.to_owned(),
+        }
+    }
+}
+
+impl BreakerError for DeleteFactError {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Unavailable { .. } | Self::UnexpectedError { .. }
+        )
+    }
+
+    fn open() -> Self {
+        Self::Unavailable {
+            inner: OPEN_DETAILS.to_owned(),
+        }
+    }
+}
+
+impl BreakerError for AddFactTagError {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Unavailable { .. } | Self::UnexpectedError { .. }
+        )
+    }
+
+    fn open() -> Self {
+        Self::Unavailable {
+            inner: OPEN_DETAILS.to_owned(),
+        }
+    }
+}
+
+impl BreakerError for RemoveFactTagError {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Unavailable { .. } | Self::UnexpectedError { .. }
+        )
+    }
+
+    fn open() -> Self {
+        Self::Unavailable {
+            inner: OPEN_DETAILS.to_owned(),
+        }
+    }
+}
+
+impl BreakerError for MigrationStatusError {
+    fn is_failure(&self) -> bool {
+        true
+    }
+
+    fn open() -> Self {
+        Self::Unavailable {
+            inner: OPEN_DETAILS.to_owned(),
+        }
+    }
+}
+
+impl BreakerError for ResyncSequenceError {
+    fn is_failure(&self) -> bool {
+        true
+    }
+
+    fn open() -> Self {
+        Self::Unavailable {
+            inner: OPEN_DETAILS.to_owned(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct BreakerState {
+    failures: u32,
+    opened_at: Option<Instant>,
+    trial_at: Option<Instant>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Admission {
+    Closed,
+    Trial,
+}
+
+struct Breaker {
+    threshold: u32,
+    cooldown: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl Breaker {
+    fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            state: Mutex::default(),
+        }
+    }
+
+    fn admits(&self) -> Option<Admission> {
+        let mut state = self.state.lock().unwrap();
+
+        let Some(opened_at) = state.opened_at else {
+            return Some(Admission::Closed);
+        };
+        if opened_at.elapsed() < self.cooldown {
+            return None;
+        }
+
+        // Half-open: only one trial call is let through. A trial that never
+        // reported back (e.g. its future was dropped) is abandoned after
+        // another cooldown.
+        if state
+            .trial_at
+            .is_some_and(|trial_at| trial_at.elapsed() < self.cooldown)
+        {
+            return None;
+        }
+        state.trial_at = Some(Instant::now());
+
+        Some(Admission::Trial)
+    }
+
+    fn record(&self, admission: Admission, failed: bool) {
+        let mut state = self.state.lock().unwrap();
+
+        if failed {
+            state.failures = state.failures.saturating_add(1);
+            if state.failures >= self.threshold {
+                if state.opened_at.is_none() {
+                    warn!(
+                        "Opening circuit breaker after {} consecutive failures",
+                        state.failures
+                    );
+                }
+                state.opened_at = Some(Instant::now());
+                state.trial_at = None;
+            }
+        } else if admission == Admission::Trial || state.opened_at.is_none() {
+            *state = BreakerState::default();
+        }
+    }
+
+    async fn call<T, E: BreakerError>(
+        &self,
+        call: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, E> {
+        let Some(admission) = self.admits() else {
+            return Err(E::open());
+        };
+
+        let result = call.await;
+        self.record(
+            admission,
+            result.as_ref().is_err_and(BreakerError::is_failure),
+        );
+
+        result
+    }
+}
+
+pub struct CircuitBreakerFactsRepository {
+    inner: Arc<dyn FactsRepository>,
+    breaker: Breaker,
+}
+
+impl CircuitBreakerFactsRepository {
+    pub fn new(inner: Arc<dyn FactsRepository>, threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            inner,
+            breaker: Breaker::new(threshold, cooldown),
+        }
+    }
+}
+
+#[async_trait]
+impl FactsRepository for CircuitBreakerFactsRepository {
+    async fn get(&self, id: FactId, public_only: bool) -> Result<Fact, GetFactError> {
+        self.breaker.call(self.inner.get(id, public_only)).await
+    }
+
+    async fn get_random(&self) -> Result<Fact, GetRandomFactError> {
+        self.breaker.call(self.inner.get_random()).await
+    }
+
+    async fn get_random_excluding(&self, id: FactId) -> Result<Fact, GetRandomFactError> {
+        self.breaker.call(self.inner.get_random_excluding(id)).await
+    }
+
+    async fn get_random_short(&self, max_body_len: usize) -> Result<Fact, GetRandomFactError> {
+        self.breaker
+            .call(self.inner.get_random_short(max_body_len))
+            .await
+    }
+
+    async fn get_random_public(&self, max_body_len: usize) -> Result<Fact, GetRandomFactError> {
+        self.breaker
+            .call(self.inner.get_random_public(max_body_len))
+            .await
+    }
+
+    async fn get_random_recent(
+        &self,
+        max_body_len: usize,
+        public_only: bool,
+    ) -> Result<Fact, GetRandomFactError> {
+        self.breaker
+            .call(self.inner.get_random_recent(max_body_len, public_only))
+            .await
+    }
+
+    async fn get_seeded(
+        &self,
+        seed: i64,
+        position: u32,
+        public_only: bool,
+    ) -> Result<Fact, GetRandomFactError> {
+        self.breaker
+            .call(self.inner.get_seeded(seed, position, public_only))
+            .await
+    }
+
+    async fn list(&self, request: &ListFactsRequest) -> Result<Vec<Fact>, ListFactsError> {
+        self.breaker.call(self.inner.list(request)).await
+    }
+
+    async fn list_with_total(
+        &self,
+        request: &ListFactsRequest,
+    ) -> Result<(Vec<Fact>, u64), ListFactsError> {
+        self.breaker.call(self.inner.list_with_total(request)).await
+    }
+
+    async fn list_by_actor(&self, actor: &str) -> Result<Vec<Fact>, ListFactsError> {
+        self.breaker.call(self.inner.list_by_actor(actor)).await
+    }
+
+    async fn list_after(
+        &self,
+        after: Option<FactId>,
+        limit: u32,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        self.breaker.call(self.inner.list_after(after, limit)).await
+    }
+
+    async fn sample(&self, n: u32, seed: u64) -> Result<Vec<Fact>, ListFactsError> {
+        self.breaker.call(self.inner.sample(n, seed)).await
+    }
+
+    async fn find(&self, request: &FindFactsRequest) -> Result<Vec<Fact>, ListFactsError> {
+        self.breaker.call(self.inner.find(request)).await
+    }
+
+    async fn latest(&self, limit: u32, public_only: bool) -> Result<Vec<Fact>, ListFactsError> {
+        self.breaker
+            .call(self.inner.latest(limit, public_only))
+            .await
+    }
+
+    async fn latest_tagged(
+        &self,
+        tag: &FactTag,
+        limit: u32,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        self.breaker
+            .call(self.inner.latest_tagged(tag, limit, public_only))
+            .await
+    }
+
+    async fn list_by_tags(
+        &self,
+        tags: &[FactTag],
+        mode: TagMatch,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        self.breaker
+            .call(self.inner.list_by_tags(tags, mode, public_only))
+            .await
+    }
+
+    async fn modified_since(
+        &self,
+        since: DateTime<Utc>,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        self.breaker
+            .call(self.inner.modified_since(since, public_only))
+            .await
+    }
+
+    async fn recent(&self, minutes: u32, public_only: bool) -> Result<Vec<Fact>, ListFactsError> {
+        self.breaker
+            .call(self.inner.recent(minutes, public_only))
+            .await
+    }
+
+    async fn count(&self, public_only: bool) -> Result<u64, ListFactsError> {
+        self.breaker.call(self.inner.count(public_only)).await
+    }
+
+    async fn extremes(&self, public_only: bool) -> Result<(Fact, Fact), GetExtremesError> {
+        self.breaker.call(self.inner.extremes(public_only)).await
+    }
+
+    async fn neighbors(
+        &self,
+        id: FactId,
+        public_only: bool,
+    ) -> Result<(Option<FactId>, Option<FactId>), ListFactsError> {
+        self.breaker
+            .call(self.inner.neighbors(id, public_only))
+            .await
+    }
+
+    async fn count_by_tag(&self, public_only: bool) -> Result<Vec<(FactTag, u64)>, ListFactsError> {
+        self.breaker
+            .call(self.inner.count_by_tag(public_only))
+            .await
+    }
+
+    async fn count_by_day(
+        &self,
+        after: Option<DateTime<Utc>>,
+        before: Option<DateTime<Utc>>,
+        public_only: bool,
+    ) -> Result<Vec<(NaiveDate, u64)>, ListFactsError> {
+        self.breaker
+            .call(self.inner.count_by_day(after, before, public_only))
+            .await
+    }
+
+    async fn digest(&self, public_only: bool) -> Result<String, ListFactsError> {
+        self.breaker.call(self.inner.digest(public_only)).await
+    }
+
+    async fn create(&self, data: &CreateFactRequest) -> Result<Fact, CreateFactError> {
+        self.breaker.call(self.inner.create(data)).await
+    }
+
+    async fn create_if_absent(&self, data: &CreateFactRequest) -> Result<Fact, CreateFactError> {
+        self.breaker.call(self.inner.create_if_absent(data)).await
+    }
+
+    async fn update(
+        &self,
+        id: FactId,
+        title: &FactTitle,
+        body: &FactBody,
+        updated_by: Option<&str>,
+        unique_body: bool,
+    ) -> Result<Fact, UpdateFactError> {
+        self.breaker
+            .call(self.inner.update(id, title, body, updated_by, unique_body))
+            .await
+    }
+
+    async fn like(&self, id: FactId, public_only: bool) -> Result<u64, UpdateFactError> {
+        self.breaker.call(self.inner.like(id, public_only)).await
+    }
+
+    async fn delete(&self, id: FactId) -> Result<(), DeleteFactError> {
+        self.breaker.call(self.inner.delete(id)).await
+    }
+
+    async fn add_tag(&self, id: FactId, tag: &FactTag) -> Result<(), AddFactTagError> {
+        self.breaker.call(self.inner.add_tag(id, tag)).await
+    }
+
+    async fn remove_tag(&self, id: FactId, tag: &FactTag) -> Result<(), RemoveFactTagError> {
+        self.breaker.call(self.inner.remove_tag(id, tag)).await
+    }
+
+    async fn migration_status(&self) -> Result<MigrationStatus, MigrationStatusError> {
+        self.breaker.call(self.inner.migration_status()).await
+    }
+
+    async fn resync_sequence(&self) -> Result<FactId, ResyncSequenceError> {
+        self.breaker.call(self.inner.resync_sequence()).await
+    }
+
+    async fn validate_all(&self) -> Result<Vec<InvalidFact>, ListFactsError> {
+        self.breaker.call(self.inner.validate_all()).await
+    }
+
+    fn pool_stats(&self) -> Option<PoolStats> {
+        self.inner.pool_stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    use tokio::{task::yield_now, time::advance};
+
+    use super::*;
+    use crate::facts::MockedFactsRepository;
+
+    const COOLDOWN: Duration = Duration::from_secs(30);
+
+    struct Backend {
+        healthy: AtomicBool,
+        calls: AtomicU32,
+    }
+
+    impl Backend {
+        fn new() -> Self {
+            Self {
+                healthy: AtomicBool::new(true),
+                calls: AtomicU32::new(0),
+            }
+        }
+
+        async fn list(&self) -> Result<Vec<Fact>, ListFactsError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            yield_now().await;
+
+            if self.healthy.load(Ordering::SeqCst) {
+                Ok(Vec::new())
+            } else {
+                Err(ListFactsError::Unavailable {
+                    inner: "connection refused".to_owned(),
+                })
+            }
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn opens_and_recovers() {
+        let breaker = Breaker::new(3, COOLDOWN);
+        let backend = Backend::new();
+
+        backend.healthy.store(false, Ordering::SeqCst);
+        for _ in 0..3 {
+            assert_eq!(
+                breaker.call(backend.list()).await,
+                Err(ListFactsError::Unavailable {
+                    inner: "connection refused".to_owned(),
+                })
+            );
+        }
+
+        backend.healthy.store(true, Ordering::SeqCst);
+        assert_eq!(
+            breaker.call(backend.list()).await,
+            Err(ListFactsError::open())
+        );
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 3);
+
+        advance(COOLDOWN).await;
+        assert_eq!(breaker.call(backend.list()).await, Ok(Vec::new()));
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 4);
+
+        backend.healthy.store(false, Ordering::SeqCst);
+        for _ in 0..2 {
+            assert!(breaker.call(backend.list()).await.is_err());
+        }
+        backend.healthy.store(true, Ordering::SeqCst);
+        assert_eq!(breaker.call(backend.list()).await, Ok(Vec::new()));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn half_open_failure_reopens() {
+        let breaker = Breaker::new(2, COOLDOWN);
+        let backend = Backend::new();
+
+        backend.healthy.store(false, Ordering::SeqCst);
+        for _ in 0..2 {
+            assert!(breaker.call(backend.list()).await.is_err());
+        }
+
+        advance(COOLDOWN).await;
+        assert!(breaker.call(backend.list()).await.is_err());
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 3);
+
+        backend.healthy.store(true, Ordering::SeqCst);
+        assert_eq!(
+            breaker.call(backend.list()).await,
+            Err(ListFactsError::open())
+        );
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn half_open_admits_single_trial() {
+        let breaker = Breaker::new(2, COOLDOWN);
+        let backend = Backend::new();
+
+        backend.healthy.store(false, Ordering::SeqCst);
+        for _ in 0..2 {
+            assert!(breaker.call(backend.list()).await.is_err());
+        }
+
+        advance(COOLDOWN).await;
+        backend.healthy.store(true, Ordering::SeqCst);
+        let (first, second, third) = tokio::join!(
+            breaker.call(backend.list()),
+            breaker.call(backend.list()),
+            breaker.call(backend.list()),
+        );
+
+        assert_eq!(first, Ok(Vec::new()));
+        assert_eq!(second, Err(ListFactsError::open()));
+        assert_eq!(third, Err(ListFactsError::open()));
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 3);
+
+        let (first, second) =
+            tokio::join!(breaker.call(backend.list()), breaker.call(backend.list()));
+
+        assert_eq!(first, Ok(Vec::new()));
+        assert_eq!(second, Ok(Vec::new()));
+        assert_eq!(backend.calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn abandoned_trial_is_retried() {
+        let breaker = Breaker::new(1, COOLDOWN);
+        let backend = Backend::new();
+
+        backend.healthy.store(false, Ordering::SeqCst);
+        assert!(breaker.call(backend.list()).await.is_err());
+
+        advance(COOLDOWN).await;
+        backend.healthy.store(true, Ordering::SeqCst);
+        assert!(matches!(breaker.admits(), Some(Admission::Trial)));
+        assert_eq!(
+            breaker.call(backend.list()).await,
+            Err(ListFactsError::open())
+        );
+
+        advance(COOLDOWN).await;
+        assert_eq!(breaker.call(backend.list()).await, Ok(Vec::new()));
+    }
+
+    #[tokio::test]
+    async fn domain_errors_are_not_failures() {
+        let breaker = Breaker::new(1, COOLDOWN);
+
+        for _ in 0..3 {
+            assert_eq!(
+                breaker
+                    .call(async { Err::<(), _>(GetRandomFactError::Empty) })
+                    .await,
+                Err(GetRandomFactError::Empty)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn delegates() {
+        let repository =
+            CircuitBreakerFactsRepository::new(Arc::new(MockedFactsRepository {}), 1, COOLDOWN);
+
+        assert_eq!(
+            repository
+                .get(FactId::new(7).unwrap(), false)
+                .await
+                .unwrap()
+                .id(),
+            FactId::new(7).unwrap()
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use sqlx::{postgres::PgListener, PgPool};
+use tokio::{
+    sync::broadcast::{self, Sender},
+    time::sleep,
+};
+use tracing::error;
+
+pub const NOTIFY_CHANGES_SETTING: &str = "facts.notify_changes";
+const CHANGES_CHANNEL: &str = "facts_changed";
+const CHANGES_CAPACITY: usize = 64;
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+pub async fn listen_changes(pool: &PgPool) -> Result<Sender<String>, sqlx::Error> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(CHANGES_CHANNEL).await?;
+
+    let (sender, _) = broadcast::channel(CHANGES_CAPACITY);
+    let changes = sender.clone();
+
+    tokio::spawn(async move {
+        loop {
+            match listener.recv().await {
+                Ok(notification) => {
+                    let _ = changes.send(notification.payload().to_owned());
+                }
+                Err(err) => {
+                    error!("Lost connection while listening to {CHANGES_CHANNEL:?}: {err}");
+                    sleep(RECONNECT_DELAY).await;
+                }
+            }
+        }
+    });
+
+    Ok(sender)
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::{Fake, Faker};
+    use tokio::time::timeout;
+
+    use super::*;
+    use crate::facts::repository::{CreateFactRequest, FactsRepository, SqlxFactsRepository};
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn create_notifies(pool: PgPool) {
+        let mut changes = listen_changes(&pool).await.unwrap().subscribe();
+        let repo = SqlxFactsRepository::new(pool);
+
+        let fact = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap();
+        let change = timeout(Duration::from_secs(5), changes.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            change,
+            format!(r#"{{"action" : "insert", "id" : {}}}"#, fact.id())
+        );
+    }
+}
@@ -1,12 +1,14 @@
 use thiserror::Error;
 
-use super::models::FactId;
+use super::models::{FactId, FactTag};
 
 #[derive(Error, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum GetFactError {
     #[error("Fact with id '{id:?}' doesn't exist in our records")]
     NoSuchFact { id: FactId },
+    #[error("Storage is temporarily unavailable: {inner}")]
+    Unavailable { inner: String },
     #[error("Something weird occured while retrieving the fact: {inner}")]
     UnexpectedError { inner: String },
 }
@@ -16,22 +18,107 @@ pub enum GetFactError {
 pub enum GetRandomFactError {
     #[error("Collection is empty, nothing to choose")]
     Empty,
+    #[error("Storage is temporarily unavailable: {inner}")]
+    Unavailable { inner: String },
     #[error("Something weird occured while retrieving the random fact: {inner}")]
     UnexpectedError { inner: String },
 }
 
+#[derive(Error, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum GetExtremesError {
+    #[error("Collection is empty, nothing to compare")]
+    Empty,
+    #[error("Storage is temporarily unavailable: {inner}")]
+    Unavailable { inner: String },
+    #[error("Something weird occured while comparing the facts: {inner}")]
+    UnexpectedError { inner: String },
+}
+
+#[derive(Error, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum ListFactsError {
+    #[error("Storage is temporarily unavailable: {inner}")]
+    Unavailable { inner: String },
+    #[error("Something weird occured while listing the facts: {inner}")]
+    UnexpectedError { inner: String },
+}
+
 #[derive(Error, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum CreateFactError {
+    #[error("Fact titled '{title}' already exists")]
+    TitleTaken { title: String },
+    #[error("Fact with the same body already exists")]
+    BodyTaken,
+    #[error("Storage is full, at most {max} facts are allowed")]
+    StorageFull { max: u64 },
+    #[error("Storage is temporarily unavailable: {inner}")]
+    Unavailable { inner: String },
     #[error("Something weird occured while creating the fact: {inner}")]
     UnexpectedError { inner: String },
 }
 
+#[derive(Error, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum UpdateFactError {
+    #[error("Fact with id '{id:?}' doesn't exist in our records")]
+    NoSuchFact { id: FactId },
+    #[error("Fact with the same body already exists")]
+    BodyTaken,
+    #[error("Storage is temporarily unavailable: {inner}")]
+    Unavailable { inner: String },
+    #[error("Something weird occured while updating the fact: {inner}")]
+    UnexpectedError { inner: String },
+}
+
 #[derive(Error, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum DeleteFactError {
     #[error("Fact with id '{id:?}' doesn't exist in our records")]
     NoSuchFact { id: FactId },
+    #[error("Storage is temporarily unavailable: {inner}")]
+    Unavailable { inner: String },
     #[error("Something weird occured while deleting the fact: {inner}")]
     UnexpectedError { inner: String },
 }
+
+#[derive(Error, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum AddFactTagError {
+    #[error("Fact with id '{id:?}' doesn't exist in our records")]
+    NoSuchFact { id: FactId },
+    #[error("Storage is temporarily unavailable: {inner}")]
+    Unavailable { inner: String },
+    #[error("Something weird occured while tagging the fact: {inner}")]
+    UnexpectedError { inner: String },
+}
+
+#[derive(Error, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum RemoveFactTagError {
+    #[error("Fact with id '{id:?}' isn't tagged with '{tag}'")]
+    NoSuchTag { id: FactId, tag: FactTag },
+    #[error("Storage is temporarily unavailable: {inner}")]
+    Unavailable { inner: String },
+    #[error("Something weird occured while untagging the fact: {inner}")]
+    UnexpectedError { inner: String },
+}
+
+#[derive(Error, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum MigrationStatusError {
+    #[error("Storage is temporarily unavailable: {inner}")]
+    Unavailable { inner: String },
+    #[error("Something weird occured while checking the migrations: {inner}")]
+    UnexpectedError { inner: String },
+}
+
+#[derive(Error, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum ResyncSequenceError {
+    #[error("Storage is temporarily unavailable: {inner}")]
+    Unavailable { inner: String },
+    #[error("Something weird occured while resyncing the id sequence: {inner}")]
+    UnexpectedError { inner: String },
+}
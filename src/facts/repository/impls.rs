@@ -1,13 +1,49 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
-use sqlx::{query_as, query_scalar, FromRow, PgPool};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{
+    migrate,
+    migrate::Migrator,
+    query,
+    query_as,
+    query_scalar,
+    FromRow,
+    PgPool,
+    Postgres,
+    Transaction,
+};
+use tokio_stream::StreamExt;
 
 use super::{
-    errors::{GetFactError, GetRandomFactError},
-    models::{Fact, FactBody, FactError, FactId, FactTitle},
+    errors::{GetExtremesError, GetFactError, GetRandomFactError},
+    models::{
+        Fact,
+        FactBody,
+        FactError,
+        FactId,
+        FactTag,
+        FactTagError,
+        FactTitle,
+        FactsDigest,
+        InvalidFact,
+        MigrationStatus,
+        PoolStats,
+        TagMatch,
+    },
+    random::{OrderByRandom, RandomStrategy},
+    AddFactTagError,
     CreateFactError,
     CreateFactRequest,
     DeleteFactError,
     FactsRepository,
+    FindFactsRequest,
+    ListFactsError,
+    ListFactsRequest,
+    MigrationStatusError,
+    RemoveFactTagError,
+    ResyncSequenceError,
+    UpdateFactError,
 };
 
 #[derive(Clone)]
@@ -17,9 +53,20 @@ const TITLE: &str = "About smoking";
 const BODY: &str = r#"The phrase "smoking kills" is a direct statement about the severe health risks of tobacco use
 Smoking is a leading cause of preventable death globally, leading to cancer, heart disease, stroke, and lung diseases like emphysema"#;
 
+impl MockedFactsRepository {
+    fn fact(id: i32) -> Result<Fact, FactError> {
+        Ok(Fact::new(
+            FactId::new(id)?,
+            &FactTitle::new(TITLE)?,
+            &FactBody::new(BODY)?,
+            DateTime::UNIX_EPOCH,
+        ))
+    }
+}
+
 #[async_trait]
 impl FactsRepository for MockedFactsRepository {
-    async fn get(&self, id: FactId) -> Result<Fact, GetFactError> {
+    async fn get(&self, id: FactId, _: bool) -> Result<Fact, GetFactError> {
         Ok(Fact::new(
             id,
             &FactTitle::new(TITLE).map_err(|err| GetFactError::UnexpectedError {
@@ -28,6 +75,7 @@ impl FactsRepository for MockedFactsRepository {
             &FactBody::new(BODY).map_err(|err| GetFactError::UnexpectedError {
                 inner: err.to_string(),
             })?,
+            DateTime::UNIX_EPOCH,
         ))
     }
 
@@ -42,9 +90,194 @@ impl FactsRepository for MockedFactsRepository {
             &FactBody::new(BODY).map_err(|err| GetRandomFactError::UnexpectedError {
                 inner: err.to_string(),
             })?,
+            DateTime::UNIX_EPOCH,
         ))
     }
 
+    async fn get_random_excluding(&self, id: FactId) -> Result<Fact, GetRandomFactError> {
+        let fact = self.get_random().await?;
+
+        if fact.id().eq(&id) {
+            Err(GetRandomFactError::Empty)
+        } else {
+            Ok(fact)
+        }
+    }
+
+    async fn get_random_short(&self, max_body_len: usize) -> Result<Fact, GetRandomFactError> {
+        if BODY.chars().count() > max_body_len {
+            return Err(GetRandomFactError::Empty);
+        }
+
+        self.get_random().await
+    }
+
+    async fn get_random_public(&self, max_body_len: usize) -> Result<Fact, GetRandomFactError> {
+        self.get_random_short(max_body_len).await
+    }
+
+    async fn get_random_recent(
+        &self,
+        max_body_len: usize,
+        _: bool,
+    ) -> Result<Fact, GetRandomFactError> {
+        self.get_random_short(max_body_len).await
+    }
+
+    async fn get_seeded(&self, _: i64, position: u32, _: bool) -> Result<Fact, GetRandomFactError> {
+        if position > 0 {
+            return Err(GetRandomFactError::Empty);
+        }
+
+        Self::fact(42).map_err(|err| GetRandomFactError::UnexpectedError {
+            inner: err.to_string(),
+        })
+    }
+
+    async fn list(&self, _: &ListFactsRequest) -> Result<Vec<Fact>, ListFactsError> {
+        Ok(vec![Fact::new(
+            FactId::new(42).map_err(|err| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })?,
+            &FactTitle::new(TITLE).map_err(|err| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })?,
+            &FactBody::new(BODY).map_err(|err| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })?,
+            DateTime::UNIX_EPOCH,
+        )])
+    }
+
+    async fn list_with_total(
+        &self,
+        request: &ListFactsRequest,
+    ) -> Result<(Vec<Fact>, u64), ListFactsError> {
+        Ok((self.list(request).await?, 1))
+    }
+
+    async fn find(&self, _: &FindFactsRequest) -> Result<Vec<Fact>, ListFactsError> {
+        Ok(vec![Self::fact(42).map_err(|err| {
+            ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            }
+        })?])
+    }
+
+    async fn list_after(&self, after: Option<FactId>, _: u32) -> Result<Vec<Fact>, ListFactsError> {
+        if after.is_some() {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![Self::fact(42).map_err(|err| {
+            ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            }
+        })?])
+    }
+
+    async fn sample(&self, n: u32, _: u64) -> Result<Vec<Fact>, ListFactsError> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![Self::fact(42).map_err(|err| {
+            ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            }
+        })?])
+    }
+
+    async fn latest(&self, _: u32, _: bool) -> Result<Vec<Fact>, ListFactsError> {
+        Ok(vec![Self::fact(42).map_err(|err| {
+            ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            }
+        })?])
+    }
+
+    async fn latest_tagged(
+        &self,
+        _: &FactTag,
+        _: u32,
+        _: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_by_tags(
+        &self,
+        _: &[FactTag],
+        _: TagMatch,
+        _: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        Ok(Vec::new())
+    }
+
+    async fn modified_since(
+        &self,
+        since: DateTime<Utc>,
+        _: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        if since >= DateTime::UNIX_EPOCH {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![Self::fact(42).map_err(|err| {
+            ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            }
+        })?])
+    }
+
+    async fn recent(&self, _: u32, _: bool) -> Result<Vec<Fact>, ListFactsError> {
+        Ok(Vec::new())
+    }
+
+    async fn count(&self, _: bool) -> Result<u64, ListFactsError> {
+        Ok(1)
+    }
+
+    async fn extremes(&self, _: bool) -> Result<(Fact, Fact), GetExtremesError> {
+        let fact = Self::fact(42).map_err(|err| GetExtremesError::UnexpectedError {
+            inner: err.to_string(),
+        })?;
+
+        Ok((fact.clone(), fact))
+    }
+
+    async fn neighbors(
+        &self,
+        _: FactId,
+        _: bool,
+    ) -> Result<(Option<FactId>, Option<FactId>), ListFactsError> {
+        Ok((None, None))
+    }
+
+    async fn count_by_tag(&self, _: bool) -> Result<Vec<(FactTag, u64)>, ListFactsError> {
+        Ok(Vec::new())
+    }
+
+    async fn count_by_day(
+        &self,
+        _: Option<DateTime<Utc>>,
+        _: Option<DateTime<Utc>>,
+        _: bool,
+    ) -> Result<Vec<(NaiveDate, u64)>, ListFactsError> {
+        Ok(Vec::new())
+    }
+
+    async fn digest(&self, _: bool) -> Result<String, ListFactsError> {
+        let mut digest = FactsDigest::default();
+        digest.update(42, DateTime::UNIX_EPOCH);
+
+        Ok(digest.finish())
+    }
+
+    async fn create_if_absent(&self, data: &CreateFactRequest) -> Result<Fact, CreateFactError> {
+        self.create(data).await
+    }
+
     async fn create(&self, _: &CreateFactRequest) -> Result<Fact, CreateFactError> {
         Ok(Fact::new(
             FactId::new(43).map_err(|err| CreateFactError::UnexpectedError {
@@ -56,9 +289,33 @@ impl FactsRepository for MockedFactsRepository {
             &FactBody::new(BODY).map_err(|err| CreateFactError::UnexpectedError {
                 inner: err.to_string(),
             })?,
+            DateTime::UNIX_EPOCH,
         ))
     }
 
+    async fn update(
+        &self,
+        id: FactId,
+        title: &FactTitle,
+        body: &FactBody,
+        _: Option<&str>,
+        _: bool,
+    ) -> Result<Fact, UpdateFactError> {
+        if i32::from(id) == 45 {
+            return Err(UpdateFactError::NoSuchFact { id });
+        }
+
+        Ok(Fact::new(id, title, body, DateTime::UNIX_EPOCH))
+    }
+
+    async fn like(&self, id: FactId, _: bool) -> Result<u64, UpdateFactError> {
+        if i32::from(id) == 45 {
+            return Err(UpdateFactError::NoSuchFact { id });
+        }
+
+        Ok(1)
+    }
+
     async fn delete(&self, id: FactId) -> Result<(), DeleteFactError> {
         let err = DeleteFactError::UnexpectedError {
             inner: "This should never happen".to_owned(),
@@ -80,24 +337,169 @@ impl FactsRepository for MockedFactsRepository {
             Ok(())
         }
     }
+
+    async fn add_tag(&self, _: FactId, _: &FactTag) -> Result<(), AddFactTagError> {
+        Ok(())
+    }
+
+    async fn remove_tag(&self, _: FactId, _: &FactTag) -> Result<(), RemoveFactTagError> {
+        Ok(())
+    }
+
+    async fn migration_status(&self) -> Result<MigrationStatus, MigrationStatusError> {
+        Ok(MigrationStatus::default())
+    }
+
+    async fn resync_sequence(&self) -> Result<FactId, ResyncSequenceError> {
+        FactId::new(43).map_err(|err| ResyncSequenceError::UnexpectedError {
+            inner: err.to_string(),
+        })
+    }
+
+    async fn validate_all(&self) -> Result<Vec<InvalidFact>, ListFactsError> {
+        Ok(Vec::new())
+    }
 }
 
+static MIGRATOR: Migrator = migrate!("./src/facts/migrations");
+const UNDEFINED_TABLE: &str = "42P01";
+
 #[derive(Clone)]
 pub struct SqlxFactsRepository {
     pool: PgPool,
+    random_strategy: Arc<dyn RandomStrategy>,
 }
 
 impl SqlxFactsRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            random_strategy: Arc::new(OrderByRandom),
+        }
+    }
+
+    pub fn random_strategy(mut self, strategy: Arc<dyn RandomStrategy>) -> Self {
+        self.random_strategy = strategy;
+        self
+    }
+
+    pub async fn start_ids_from(&self, start: FactId) -> Result<(), sqlx::Error> {
+        query!(
+            r"
+SELECT setval('facts_id_seq', $1, false)
+FROM facts_id_seq
+WHERE $1 > last_value
+        ",
+            i64::from(i32::from(start)),
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(())
     }
 }
 
-#[derive(FromRow)]
-struct SqlxFact {
+/// Advisory lock namespace serialising inserts of the same body hash.
+const BODY_HASH_LOCK: i32 = 1;
+/// Advisory lock namespace serialising conditional inserts of the same title.
+const TITLE_LOCK: i32 = 2;
+
+async fn ensure_unique_body(
+    transaction: &mut Transaction<'_, Postgres>,
+    body: &FactBody,
+) -> Result<(), CreateFactError> {
+    if body_taken(transaction, body, None).await? {
+        return Err(CreateFactError::BodyTaken);
+    }
+
+    Ok(())
+}
+
+/// Checks whether a fact other than `except` already holds `body`, keeping
+/// the body hash locked until the transaction ends.
+async fn body_taken(
+    transaction: &mut Transaction<'_, Postgres>,
+    body: &FactBody,
+    except: Option<FactId>,
+) -> Result<bool, sqlx::Error> {
+    let body = String::from(body.to_owned());
+
+    // The snapshot is taken per statement, so the check has to run after the
+    // lock is granted to see rows committed by the previous holder.
+    query!(
+        r#"SELECT true AS "locked!" FROM pg_advisory_xact_lock($1, hashtext(md5($2)))"#,
+        BODY_HASH_LOCK,
+        body,
+    )
+    .fetch_one(&mut **transaction)
+    .await?;
+    query_scalar!(
+        r#"
+SELECT EXISTS (
+    SELECT 1 FROM facts WHERE body_hash = md5($1) AND id IS DISTINCT FROM $2
+) AS "taken!"
+        "#,
+        body,
+        except.map(i32::from),
+    )
+    .fetch_one(&mut **transaction)
+    .await
+}
+
+async fn ensure_capacity(
+    transaction: &mut Transaction<'_, Postgres>,
+    max_total: Option<u64>,
+) -> Result<(), CreateFactError> {
+    let Some(max) = max_total else {
+        return Ok(());
+    };
+
+    query!("LOCK TABLE facts IN SHARE ROW EXCLUSIVE MODE")
+        .execute(&mut **transaction)
+        .await?;
+    let total = query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM facts"#)
+        .fetch_one(&mut **transaction)
+        .await?;
+
+    if total.cast_unsigned() >= max {
+        return Err(CreateFactError::StorageFull { max });
+    }
+
+    Ok(())
+}
+
+async fn insert_tags(
+    transaction: &mut Transaction<'_, Postgres>,
     id: i32,
-    title: String,
-    body: String,
+    tags: &[FactTag],
+) -> Result<(), sqlx::Error> {
+    if tags.is_empty() {
+        return Ok(());
+    }
+
+    query!(
+        r"
+INSERT INTO fact_tags (fact_id, tag)
+SELECT $1, UNNEST($2::text[])
+ON CONFLICT DO NOTHING
+        ",
+        id,
+        &tags.iter().map(ToString::to_string).collect::<Vec<_>>(),
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    Ok(())
+}
+
+#[derive(FromRow)]
+pub struct SqlxFact {
+    pub(super) id: i32,
+    pub(super) title: String,
+    pub(super) body: String,
+    pub(super) created_at: DateTime<Utc>,
+    pub(super) created_by: Option<String>,
+    pub(super) updated_by: Option<String>,
 }
 
 impl TryFrom<SqlxFact> for Fact {
@@ -108,7 +510,9 @@ impl TryFrom<SqlxFact> for Fact {
             FactId::new(value.id)?,
             &FactTitle::new(&value.title)?,
             &FactBody::new(&value.body)?,
-        ))
+            value.created_at,
+        )
+        .with_actors(value.created_by, value.updated_by))
     }
 }
 
@@ -118,30 +522,175 @@ impl From<Fact> for SqlxFact {
             id: val.id().into(),
             title: val.title().to_owned().into(),
             body: val.body().to_owned().into(),
+            created_at: val.created_at(),
+            created_by: val.created_by().map(ToOwned::to_owned),
+            updated_by: val.updated_by().map(ToOwned::to_owned),
+        }
+    }
+}
+
+impl From<sqlx::Error> for GetFactError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::PoolTimedOut => Self::Unavailable {
+                inner: value.to_string(),
+            },
+            _ => Self::UnexpectedError {
+                inner: value.to_string(),
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for GetRandomFactError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::PoolTimedOut => Self::Unavailable {
+                inner: value.to_string(),
+            },
+            _ => Self::UnexpectedError {
+                inner: value.to_string(),
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for GetExtremesError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::PoolTimedOut => Self::Unavailable {
+                inner: value.to_string(),
+            },
+            _ => Self::UnexpectedError {
+                inner: value.to_string(),
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for ListFactsError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::PoolTimedOut => Self::Unavailable {
+                inner: value.to_string(),
+            },
+            _ => Self::UnexpectedError {
+                inner: value.to_string(),
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for CreateFactError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::PoolTimedOut => Self::Unavailable {
+                inner: value.to_string(),
+            },
+            _ => Self::UnexpectedError {
+                inner: value.to_string(),
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for UpdateFactError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::PoolTimedOut => Self::Unavailable {
+                inner: value.to_string(),
+            },
+            _ => Self::UnexpectedError {
+                inner: value.to_string(),
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for DeleteFactError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::PoolTimedOut => Self::Unavailable {
+                inner: value.to_string(),
+            },
+            _ => Self::UnexpectedError {
+                inner: value.to_string(),
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for AddFactTagError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::PoolTimedOut => Self::Unavailable {
+                inner: value.to_string(),
+            },
+            _ => Self::UnexpectedError {
+                inner: value.to_string(),
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for MigrationStatusError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::PoolTimedOut => Self::Unavailable {
+                inner: value.to_string(),
+            },
+            _ => Self::UnexpectedError {
+                inner: value.to_string(),
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for ResyncSequenceError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::PoolTimedOut => Self::Unavailable {
+                inner: value.to_string(),
+            },
+            _ => Self::UnexpectedError {
+                inner: value.to_string(),
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for RemoveFactTagError {
+    fn from(value: sqlx::Error) -> Self {
+        match value {
+            sqlx::Error::PoolTimedOut => Self::Unavailable {
+                inner: value.to_string(),
+            },
+            _ => Self::UnexpectedError {
+                inner: value.to_string(),
+            },
         }
     }
 }
 
 #[async_trait]
 impl FactsRepository for SqlxFactsRepository {
-    async fn get(&self, id: FactId) -> Result<Fact, GetFactError> {
+    async fn get(&self, id: FactId, public_only: bool) -> Result<Fact, GetFactError> {
         let result = query_as!(
             SqlxFact,
             r"
 SELECT
-  id, title, body
+  id, title, body, created_at, created_by, updated_by
 FROM facts
-WHERE id = $1
+WHERE id = $1 AND (public OR NOT $2)
         ",
-            i32::from(id)
+            i32::from(id),
+            public_only,
         )
         .fetch_optional(&self.pool)
         .await
         .transpose()
         .ok_or(GetFactError::NoSuchFact { id })?
-        .map_err(|err| GetFactError::UnexpectedError {
-            inner: err.to_string(),
-        })?;
+        .map_err(GetFactError::from)?;
 
         result
             .try_into()
@@ -151,23 +700,37 @@ WHERE id = $1
     }
 
     async fn get_random(&self) -> Result<Fact, GetRandomFactError> {
+        let result = self
+            .random_strategy
+            .choose(&self.pool, i32::MAX, false)
+            .await?
+            .ok_or(GetRandomFactError::Empty)?;
+
+        result
+            .try_into()
+            .map_err(|err: FactError| GetRandomFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn get_random_excluding(&self, id: FactId) -> Result<Fact, GetRandomFactError> {
         let result = query_as!(
             SqlxFact,
             r"
 SELECT
-  id, title, body
+  id, title, body, created_at, created_by, updated_by
 FROM facts
+WHERE id <> $1
 ORDER BY random()
 LIMIT 1
         ",
+            i32::from(id)
         )
         .fetch_optional(&self.pool)
         .await
         .transpose()
         .ok_or(GetRandomFactError::Empty)?
-        .map_err(|err| GetRandomFactError::UnexpectedError {
-            inner: err.to_string(),
-        })?;
+        .map_err(GetRandomFactError::from)?;
 
         result
             .try_into()
@@ -176,219 +739,2234 @@ LIMIT 1
             })
     }
 
-    async fn create(&self, data: &CreateFactRequest) -> Result<Fact, CreateFactError> {
+    async fn get_random_short(&self, max_body_len: usize) -> Result<Fact, GetRandomFactError> {
+        let result = self
+            .random_strategy
+            .choose(
+                &self.pool,
+                i32::try_from(max_body_len).unwrap_or(i32::MAX),
+                false,
+            )
+            .await?
+            .ok_or(GetRandomFactError::Empty)?;
+
+        result
+            .try_into()
+            .map_err(|err: FactError| GetRandomFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn get_random_public(&self, max_body_len: usize) -> Result<Fact, GetRandomFactError> {
+        let result = self
+            .random_strategy
+            .choose(
+                &self.pool,
+                i32::try_from(max_body_len).unwrap_or(i32::MAX),
+                true,
+            )
+            .await?
+            .ok_or(GetRandomFactError::Empty)?;
+
+        result
+            .try_into()
+            .map_err(|err: FactError| GetRandomFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn get_random_recent(
+        &self,
+        max_body_len: usize,
+        public_only: bool,
+    ) -> Result<Fact, GetRandomFactError> {
+        // Efraimidis-Spirakis sampling: each row draws an exponential key
+        // scaled by 1 + age in hours, so the pick probability falls off with
+        // age as 1 / (1 + age in hours).
         let result = query_as!(
             SqlxFact,
             r"
-INSERT INTO facts (title, body)
-VALUES ($1, $2)
-RETURNING id, title, body
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE length(body) <= $1 AND (public OR NOT $2)
+ORDER BY -ln(1.0 - random()) * (1.0 + greatest(extract(epoch FROM now() - created_at), 0) / 3600)
+LIMIT 1
         ",
-            String::from(data.title().to_owned()),
-            String::from(data.body().to_owned()),
+            i32::try_from(max_body_len).unwrap_or(i32::MAX),
+            public_only,
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await
-        .map_err(|err| CreateFactError::UnexpectedError {
-            inner: err.to_string(),
-        })?;
+        .transpose()
+        .ok_or(GetRandomFactError::Empty)?
+        .map_err(GetRandomFactError::from)?;
 
         result
             .try_into()
-            .map_err(|err: FactError| CreateFactError::UnexpectedError {
+            .map_err(|err: FactError| GetRandomFactError::UnexpectedError {
                 inner: err.to_string(),
             })
     }
 
-    async fn delete(&self, id: FactId) -> Result<(), DeleteFactError> {
-        query_scalar!(
+    async fn get_seeded(
+        &self,
+        seed: i64,
+        position: u32,
+        public_only: bool,
+    ) -> Result<Fact, GetRandomFactError> {
+        let result = query_as!(
+            SqlxFact,
             r"
-DELETE FROM facts
-WHERE id = $1
-RETURNING id
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE public OR NOT $3
+ORDER BY md5(id::text || ':' || $1::bigint::text), id
+OFFSET $2
+LIMIT 1
         ",
-            i32::from(id)
+            seed,
+            i64::from(position),
+            public_only,
         )
         .fetch_optional(&self.pool)
         .await
         .transpose()
-        .ok_or(DeleteFactError::NoSuchFact { id })?
-        .map_err(|err| DeleteFactError::UnexpectedError {
-            inner: err.to_string(),
-        })?;
+        .ok_or(GetRandomFactError::Empty)?
+        .map_err(GetRandomFactError::from)?;
 
-        Ok(())
+        result
+            .try_into()
+            .map_err(|err: FactError| GetRandomFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn list(&self, request: &ListFactsRequest) -> Result<Vec<Fact>, ListFactsError> {
+        let tags = request
+            .tags()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let result = query_as!(
+            SqlxFact,
+            r"
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE
+  ($3::timestamptz IS NULL OR created_at > $3)
+  AND ($4::timestamptz IS NULL OR created_at < $4)
+  AND ($5::text IS NULL OR created_by = $5)
+  AND (public OR NOT $6)
+  AND (
+    cardinality($7::text[]) = 0
+    OR (
+      SELECT COUNT(DISTINCT tag) FROM fact_tags WHERE fact_id = facts.id AND tag = ANY($7)
+    ) >= CASE WHEN $8 THEN (SELECT COUNT(DISTINCT tag) FROM unnest($7::text[]) AS tag) ELSE 1 END
+  )
+ORDER BY id
+LIMIT $1
+OFFSET $2
+        ",
+            i64::from(request.limit()),
+            i64::from(request.offset()),
+            request.created_after(),
+            request.created_before(),
+            request.created_by(),
+            request.public_only(),
+            &tags,
+            request.tag_match() == TagMatch::All,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        result
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn list_with_total(
+        &self,
+        request: &ListFactsRequest,
+    ) -> Result<(Vec<Fact>, u64), ListFactsError> {
+        let tags = request
+            .tags()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let rows = query!(
+            r#"
+SELECT
+  id, title, body, created_at, created_by, updated_by, COUNT(*) OVER () AS "total!"
+FROM facts
+WHERE
+  ($3::timestamptz IS NULL OR created_at > $3)
+  AND ($4::timestamptz IS NULL OR created_at < $4)
+  AND ($5::text IS NULL OR created_by = $5)
+  AND (public OR NOT $6)
+  AND (
+    cardinality($7::text[]) = 0
+    OR (
+      SELECT COUNT(DISTINCT tag) FROM fact_tags WHERE fact_id = facts.id AND tag = ANY($7)
+    ) >= CASE WHEN $8 THEN (SELECT COUNT(DISTINCT tag) FROM unnest($7::text[]) AS tag) ELSE 1 END
+  )
+ORDER BY id
+LIMIT $1
+OFFSET $2
+        "#,
+            i64::from(request.limit()),
+            i64::from(request.offset()),
+            request.created_after(),
+            request.created_before(),
+            request.created_by(),
+            request.public_only(),
+            &tags,
+            request.tag_match() == TagMatch::All,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let total = match rows.first() {
+            Some(row) => row.total,
+            None => {
+                query_scalar!(
+                    r#"
+SELECT
+  COUNT(*) AS "total!"
+FROM facts
+WHERE
+  ($1::timestamptz IS NULL OR created_at > $1)
+  AND ($2::timestamptz IS NULL OR created_at < $2)
+  AND ($3::text IS NULL OR created_by = $3)
+  AND (public OR NOT $4)
+  AND (
+    cardinality($5::text[]) = 0
+    OR (
+      SELECT COUNT(DISTINCT tag) FROM fact_tags WHERE fact_id = facts.id AND tag = ANY($5)
+    ) >= CASE WHEN $6 THEN (SELECT COUNT(DISTINCT tag) FROM unnest($5::text[]) AS tag) ELSE 1 END
+  )
+        "#,
+                    request.created_after(),
+                    request.created_before(),
+                    request.created_by(),
+                    request.public_only(),
+                    &tags,
+                    request.tag_match() == TagMatch::All,
+                )
+                .fetch_one(&self.pool)
+                .await?
+            }
+        };
+
+        let facts = rows
+            .into_iter()
+            .map(|row| {
+                SqlxFact {
+                    id: row.id,
+                    title: row.title,
+                    body: row.body,
+                    created_at: row.created_at,
+                    created_by: row.created_by,
+                    updated_by: row.updated_by,
+                }
+                .try_into()
+            })
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })?;
+
+        Ok((facts, total.cast_unsigned()))
+    }
+
+    async fn find(&self, request: &FindFactsRequest) -> Result<Vec<Fact>, ListFactsError> {
+        let result = query_as!(
+            SqlxFact,
+            r"
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE
+  ($1::text IS NULL OR strpos(lower(title), lower($1)) > 0)
+  AND ($2::text IS NULL OR strpos(lower(body), lower($2)) > 0)
+  AND (
+    $3::text IS NULL
+    OR EXISTS (SELECT 1 FROM fact_tags WHERE fact_id = facts.id AND tag = $3)
+  )
+  AND (public OR NOT $5)
+ORDER BY id
+LIMIT $4
+        ",
+            request.title_contains(),
+            request.body_contains(),
+            request.tag().map(ToString::to_string),
+            i64::from(request.limit()),
+            request.public_only(),
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        result
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn list_after(
+        &self,
+        after: Option<FactId>,
+        limit: u32,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        let result = query_as!(
+            SqlxFact,
+            r"
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE $1::int IS NULL OR id > $1
+ORDER BY id
+LIMIT $2
+        ",
+            after.map(i32::from),
+            i64::from(limit),
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        result
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn sample(&self, n: u32, seed: u64) -> Result<Vec<Fact>, ListFactsError> {
+        let result = query_as!(
+            SqlxFact,
+            r"
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+ORDER BY md5(id::text || ':' || $1::bigint::text), id
+LIMIT $2
+        ",
+            seed.cast_signed(),
+            i64::from(n),
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        result
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn latest(&self, limit: u32, public_only: bool) -> Result<Vec<Fact>, ListFactsError> {
+        let result = query_as!(
+            SqlxFact,
+            r"
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE public OR NOT $2
+ORDER BY created_at DESC, id DESC
+LIMIT $1
+        ",
+            i64::from(limit),
+            public_only,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        result
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn latest_tagged(
+        &self,
+        tag: &FactTag,
+        limit: u32,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        let result = query_as!(
+            SqlxFact,
+            r"
+SELECT
+  facts.id, facts.title, facts.body, facts.created_at, facts.created_by, facts.updated_by
+FROM facts
+JOIN fact_tags ON fact_tags.fact_id = facts.id
+WHERE fact_tags.tag = $1 AND (facts.public OR NOT $3)
+ORDER BY facts.created_at DESC, facts.id DESC
+LIMIT $2
+        ",
+            tag.to_string(),
+            i64::from(limit),
+            public_only,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        result
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn list_by_tags(
+        &self,
+        tags: &[FactTag],
+        mode: TagMatch,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        let tags = tags.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let result = query_as!(
+            SqlxFact,
+            r"
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE (public OR NOT $3)
+AND (
+  SELECT COUNT(DISTINCT tag) FROM fact_tags WHERE fact_id = facts.id AND tag = ANY($1)
+) >= CASE WHEN $2 THEN (SELECT COUNT(DISTINCT tag) FROM unnest($1::text[]) AS tag) ELSE 1 END
+ORDER BY id
+        ",
+            &tags,
+            mode == TagMatch::All,
+            public_only,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        result
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn modified_since(
+        &self,
+        since: DateTime<Utc>,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        let result = query_as!(
+            SqlxFact,
+            r"
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE updated_at > $1 AND (public OR NOT $2)
+ORDER BY updated_at, id
+        ",
+            since,
+            public_only,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        result
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn recent(&self, minutes: u32, public_only: bool) -> Result<Vec<Fact>, ListFactsError> {
+        let result = query_as!(
+            SqlxFact,
+            r"
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE created_at > now() - make_interval(mins => $1) AND (public OR NOT $2)
+ORDER BY created_at, id
+        ",
+            i32::try_from(minutes).unwrap_or(i32::MAX),
+            public_only,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        result
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn count(&self, public_only: bool) -> Result<u64, ListFactsError> {
+        let count = query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM facts WHERE public OR NOT $1"#,
+            public_only,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.cast_unsigned())
+    }
+
+    async fn extremes(&self, public_only: bool) -> Result<(Fact, Fact), GetExtremesError> {
+        let rows = query!(
+            r#"
+(SELECT
+  id AS "id!", title AS "title!", body AS "body!", created_at AS "created_at!",
+  created_by, updated_by, true AS "longest!"
+FROM facts
+WHERE public OR NOT $1
+ORDER BY length(body) DESC, id
+LIMIT 1)
+UNION ALL
+(SELECT id, title, body, created_at, created_by, updated_by, false
+FROM facts
+WHERE public OR NOT $1
+ORDER BY length(body), id
+LIMIT 1)
+        "#,
+            public_only,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut longest = None;
+        let mut shortest = None;
+        for row in rows {
+            let fact = Fact::try_from(SqlxFact {
+                id: row.id,
+                title: row.title,
+                body: row.body,
+                created_at: row.created_at,
+                created_by: row.created_by,
+                updated_by: row.updated_by,
+            })
+            .map_err(|err| GetExtremesError::UnexpectedError {
+                inner: err.to_string(),
+            })?;
+
+            if row.longest {
+                longest = Some(fact);
+            } else {
+                shortest = Some(fact);
+            }
+        }
+
+        longest.zip(shortest).ok_or(GetExtremesError::Empty)
+    }
+
+    async fn neighbors(
+        &self,
+        id: FactId,
+        public_only: bool,
+    ) -> Result<(Option<FactId>, Option<FactId>), ListFactsError> {
+        let row = query!(
+            r"
+SELECT
+  (SELECT MAX(id) FROM facts WHERE id < $1 AND (public OR NOT $2)) AS previous,
+  (SELECT MIN(id) FROM facts WHERE id > $1 AND (public OR NOT $2)) AS next
+        ",
+            i32::from(id),
+            public_only,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let neighbor = |id: Option<i32>| {
+            id.map(FactId::new)
+                .transpose()
+                .map_err(|err| ListFactsError::UnexpectedError {
+                    inner: err.to_string(),
+                })
+        };
+
+        Ok((neighbor(row.previous)?, neighbor(row.next)?))
+    }
+
+    async fn count_by_tag(&self, public_only: bool) -> Result<Vec<(FactTag, u64)>, ListFactsError> {
+        let rows = query!(
+            r#"
+SELECT
+  tag, COUNT(*) AS "count!"
+FROM fact_tags
+JOIN facts ON facts.id = fact_tags.fact_id
+WHERE public OR NOT $1
+GROUP BY tag
+ORDER BY 2 DESC, tag
+        "#,
+            public_only,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| Ok((FactTag::new(&row.tag)?, row.count.cast_unsigned())))
+            .collect::<Result<_, FactTagError>>()
+            .map_err(|err| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn count_by_day(
+        &self,
+        after: Option<DateTime<Utc>>,
+        before: Option<DateTime<Utc>>,
+        public_only: bool,
+    ) -> Result<Vec<(NaiveDate, u64)>, ListFactsError> {
+        let rows = query!(
+            r#"
+SELECT
+  date_trunc('day', created_at AT TIME ZONE 'UTC')::date AS "day!", COUNT(*) AS "count!"
+FROM facts
+WHERE
+  ($1::timestamptz IS NULL OR created_at > $1)
+  AND ($2::timestamptz IS NULL OR created_at < $2)
+  AND (public OR NOT $3)
+GROUP BY 1
+ORDER BY 1
+        "#,
+            after,
+            before,
+            public_only,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.day, row.count.cast_unsigned()))
+            .collect())
+    }
+
+    async fn create(&self, data: &CreateFactRequest) -> Result<Fact, CreateFactError> {
+        let mut transaction = self.pool.begin().await?;
+        if data.unique_body() {
+            ensure_unique_body(&mut transaction, data.body()).await?;
+        }
+        ensure_capacity(&mut transaction, data.max_total()).await?;
+        let result = query_as!(
+            SqlxFact,
+            r"
+INSERT INTO facts (title, body, created_by, public)
+VALUES ($1, $2, $3, $4)
+RETURNING id, title, body, created_at, created_by, updated_by
+        ",
+            String::from(data.title().to_owned()),
+            String::from(data.body().to_owned()),
+            data.created_by(),
+            data.public(),
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(CreateFactError::from)?;
+        insert_tags(&mut transaction, result.id, data.tags()).await?;
+        transaction.commit().await?;
+
+        result
+            .try_into()
+            .map_err(|err: FactError| CreateFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn create_if_absent(&self, data: &CreateFactRequest) -> Result<Fact, CreateFactError> {
+        let mut transaction = self.pool.begin().await?;
+        // Without a unique constraint on title, NOT EXISTS alone lets two
+        // concurrent inserts of the same title both succeed.
+        query!(
+            r#"SELECT true AS "locked!" FROM pg_advisory_xact_lock($1, hashtext($2))"#,
+            TITLE_LOCK,
+            String::from(data.title().to_owned()),
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+        if data.unique_body() {
+            ensure_unique_body(&mut transaction, data.body()).await?;
+        }
+        ensure_capacity(&mut transaction, data.max_total()).await?;
+        let result = query_as!(
+            SqlxFact,
+            r"
+INSERT INTO facts (title, body, created_by, public)
+SELECT $1::text, $2::text, $3::text, $4::boolean
+WHERE NOT EXISTS (SELECT 1 FROM facts WHERE title = $1::text)
+RETURNING id, title, body, created_at, created_by, updated_by
+        ",
+            String::from(data.title().to_owned()),
+            String::from(data.body().to_owned()),
+            data.created_by(),
+            data.public(),
+        )
+        .fetch_optional(&mut *transaction)
+        .await
+        .transpose()
+        .ok_or(CreateFactError::TitleTaken {
+            title: data.title().to_owned().into(),
+        })?
+        .map_err(CreateFactError::from)?;
+        insert_tags(&mut transaction, result.id, data.tags()).await?;
+        transaction.commit().await?;
+
+        result
+            .try_into()
+            .map_err(|err: FactError| CreateFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn update(
+        &self,
+        id: FactId,
+        title: &FactTitle,
+        body: &FactBody,
+        updated_by: Option<&str>,
+        unique_body: bool,
+    ) -> Result<Fact, UpdateFactError> {
+        let mut transaction = self.pool.begin().await?;
+        if unique_body && body_taken(&mut transaction, body, Some(id)).await? {
+            return Err(UpdateFactError::BodyTaken);
+        }
+
+        let result = query_as!(
+            SqlxFact,
+            r"
+UPDATE facts
+SET title = $2, body = $3, updated_at = now(), updated_by = $4
+WHERE id = $1
+RETURNING id, title, body, created_at, created_by, updated_by
+        ",
+            i32::from(id),
+            String::from(title.to_owned()),
+            String::from(body.to_owned()),
+            updated_by,
+        )
+        .fetch_optional(&mut *transaction)
+        .await?
+        .ok_or(UpdateFactError::NoSuchFact { id })?;
+        transaction.commit().await?;
+
+        result
+            .try_into()
+            .map_err(|err: FactError| UpdateFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn like(&self, id: FactId, public_only: bool) -> Result<u64, UpdateFactError> {
+        let likes = query_scalar!(
+            r"
+UPDATE facts
+SET likes = likes + 1
+WHERE id = $1 AND (public OR NOT $2)
+RETURNING likes
+        ",
+            i32::from(id),
+            public_only,
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(UpdateFactError::NoSuchFact { id })?;
+
+        Ok(likes.cast_unsigned())
+    }
+
+    async fn delete(&self, id: FactId) -> Result<(), DeleteFactError> {
+        query_scalar!(
+            r"
+DELETE FROM facts
+WHERE id = $1
+RETURNING id
+        ",
+            i32::from(id)
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .transpose()
+        .ok_or(DeleteFactError::NoSuchFact { id })?
+        .map_err(DeleteFactError::from)?;
+
+        Ok(())
+    }
+
+    async fn add_tag(&self, id: FactId, tag: &FactTag) -> Result<(), AddFactTagError> {
+        query!(
+            r"
+INSERT INTO fact_tags (fact_id, tag)
+VALUES ($1, $2)
+ON CONFLICT DO NOTHING
+        ",
+            i32::from(id),
+            String::from(tag.to_owned()),
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|err| match err.as_database_error() {
+            Some(db_err) if db_err.is_foreign_key_violation() => AddFactTagError::NoSuchFact { id },
+            _ => AddFactTagError::from(err),
+        })?;
+
+        Ok(())
+    }
+
+    async fn remove_tag(&self, id: FactId, tag: &FactTag) -> Result<(), RemoveFactTagError> {
+        query_scalar!(
+            r"
+DELETE FROM fact_tags
+WHERE fact_id = $1 AND tag = $2
+RETURNING fact_id
+        ",
+            i32::from(id),
+            String::from(tag.to_owned()),
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .transpose()
+        .ok_or(RemoveFactTagError::NoSuchTag {
+            id,
+            tag: tag.to_owned(),
+        })?
+        .map_err(RemoveFactTagError::from)?;
+
+        Ok(())
+    }
+
+    async fn migration_status(&self) -> Result<MigrationStatus, MigrationStatusError> {
+        let applied: Vec<i64> = match sqlx::query_scalar(
+            "SELECT version FROM _sqlx_migrations WHERE success ORDER BY version",
+        )
+        .fetch_all(&self.pool)
+        .await
+        {
+            Ok(applied) => applied,
+            Err(sqlx::Error::Database(err)) if err.code().as_deref() == Some(UNDEFINED_TABLE) => {
+                Vec::new()
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let pending = MIGRATOR
+            .iter()
+            .filter(|migration| migration.migration_type.is_up_migration())
+            .map(|migration| migration.version)
+            .filter(|version| !applied.contains(version))
+            .collect();
+
+        Ok(MigrationStatus::new(applied, pending))
+    }
+
+    async fn resync_sequence(&self) -> Result<FactId, ResyncSequenceError> {
+        let mut transaction = self.pool.begin().await?;
+
+        query!("LOCK TABLE facts IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *transaction)
+            .await?;
+        let next = query_scalar!(
+            r#"
+SELECT
+  setval('facts_id_seq', COALESCE(MAX(id), 0)::bigint + 1, false) AS "next!"
+FROM facts
+        "#,
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        i32::try_from(next)
+            .map_err(|err| err.to_string())
+            .and_then(|next| FactId::new(next).map_err(|err| err.to_string()))
+            .map_err(|inner| ResyncSequenceError::UnexpectedError { inner })
+    }
+
+    async fn digest(&self, public_only: bool) -> Result<String, ListFactsError> {
+        let mut rows = query!(
+            "SELECT id, updated_at FROM facts WHERE public OR NOT $1 ORDER BY id",
+            public_only,
+        )
+        .fetch(&self.pool);
+        let mut digest = FactsDigest::default();
+
+        while let Some(row) = rows.next().await {
+            let row = row?;
+            digest.update(row.id, row.updated_at);
+        }
+
+        Ok(digest.finish())
+    }
+
+    async fn validate_all(&self) -> Result<Vec<InvalidFact>, ListFactsError> {
+        let mut rows = query_as!(
+            SqlxFact,
+            "SELECT id, title, body, created_at, created_by, updated_by FROM facts ORDER BY id",
+        )
+        .fetch(&self.pool);
+        let mut invalid = Vec::new();
+
+        while let Some(row) = rows.next().await {
+            let row = row?;
+            invalid.extend(InvalidFact::check(row.id, &row.title, &row.body));
+        }
+
+        Ok(invalid)
+    }
+
+    fn pool_stats(&self) -> Option<PoolStats> {
+        Some(PoolStats::new(
+            self.pool.size(),
+            u32::try_from(self.pool.num_idle()).unwrap_or(u32::MAX),
+        ))
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use fake::{Fake, Faker};
-    use sqlx::{query, query_scalar};
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use fake::{Fake, Faker};
+
+    use super::*;
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get(pool: PgPool) {
+        let fake = Faker.fake::<Fact>();
+        let entity: SqlxFact = fake.clone().into();
+
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            entity.title,
+            entity.body,
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqlxFactsRepository::new(pool);
+
+        let result: Fact = repo.get(FactId::new(id).unwrap(), false).await.unwrap();
+
+        assert_eq!(fake.body(), result.body());
+        assert_eq!(fake.title(), result.title());
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_non_existent(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let id = Faker.fake();
+        let result = repo.get(id, false).await;
+
+        assert_eq!(result, Err(GetFactError::NoSuchFact { id }));
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_random_from_empty(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let result = repo.get_random().await;
+
+        assert_eq!(result, Err(GetRandomFactError::Empty));
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_random_from_one_element(pool: PgPool) {
+        let fake = Faker.fake::<Fact>();
+        let entity: SqlxFact = fake.clone().into();
+
+        query!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2)",
+            entity.title,
+            entity.body,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqlxFactsRepository::new(pool);
+
+        let result = repo.get_random().await.unwrap();
+
+        assert_eq!(fake.title(), result.title());
+        assert_eq!(fake.body(), result.body());
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_random_from_one_corrupted_element(pool: PgPool) {
+        let fake = Faker.fake::<Fact>();
+        let entity: SqlxFact = fake.clone().into();
+
+        query!(
+            "INSERT INTO facts (id, title, body) VALUES ($1, $2, $3)",
+            0,
+            entity.title,
+            entity.body,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqlxFactsRepository::new(pool);
+
+        assert!(matches!(
+            repo.get_random().await,
+            Err(GetRandomFactError::UnexpectedError { inner: _ })
+        ));
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_random(pool: PgPool) {
+        for _ in 0..32 {
+            let fake = Faker.fake::<Fact>();
+            let entity: SqlxFact = fake.clone().into();
+
+            query!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2)",
+                entity.title,
+                entity.body,
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let repo = SqlxFactsRepository::new(pool);
+
+        repo.get_random().await.unwrap();
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_random_short(pool: PgPool) {
+        let mut short = HashSet::new();
+
+        for length in [5, 10, 200, 1000] {
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                "foo",
+                "ы".repeat(length),
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            if length <= 10 {
+                short.insert(id);
+            }
+        }
+
+        let repo = SqlxFactsRepository::new(pool);
+
+        for _ in 0..20 {
+            let fact = repo.get_random_short(10).await.unwrap();
+
+            assert!(short.contains(&i32::from(fact.id())));
+        }
+
+        assert_eq!(
+            repo.get_random_short(4).await,
+            Err(GetRandomFactError::Empty)
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_random_recent(pool: PgPool) {
+        let now = Utc::now();
+        let mut ids = Vec::new();
+
+        for created_at in [now, now - chrono::TimeDelta::hours(3)] {
+            ids.push(
+                query_scalar!(
+                    "INSERT INTO facts (title, body, created_at) VALUES ($1, $2, $3) RETURNING id",
+                    "foo",
+                    "bar",
+                    created_at,
+                )
+                .fetch_one(&pool)
+                .await
+                .unwrap(),
+            );
+        }
+
+        let repo = SqlxFactsRepository::new(pool.clone());
+        let mut recent = 0;
+
+        for _ in 0..500 {
+            let fact = repo.get_random_recent(usize::MAX, false).await.unwrap();
+
+            if i32::from(fact.id()) == ids[0] {
+                recent += 1;
+            }
+        }
+
+        // Weights are 1 and 1/4, so the recent fact wins 80% of the draws.
+        assert!((360..=440).contains(&recent), "{recent}");
+
+        query!("UPDATE facts SET public = false WHERE id = $1", ids[0])
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for _ in 0..20 {
+            let fact = repo.get_random_recent(usize::MAX, true).await.unwrap();
+
+            assert_eq!(i32::from(fact.id()), ids[1]);
+        }
+
+        assert_eq!(
+            repo.get_random_recent(2, false).await,
+            Err(GetRandomFactError::Empty)
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_seeded(pool: PgPool) {
+        for _ in 0..32 {
+            let fake = Faker.fake::<Fact>();
+            let entity: SqlxFact = fake.clone().into();
+
+            query!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2)",
+                entity.title,
+                entity.body,
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let repo = SqlxFactsRepository::new(pool);
+        let fact = repo.get_seeded(202_401, 0, false).await.unwrap();
+
+        for _ in 0..8 {
+            assert_eq!(repo.get_seeded(202_401, 0, false).await.unwrap(), fact);
+        }
+
+        let mut ids = HashSet::new();
+        for seed in 202_401..202_417 {
+            ids.insert(i32::from(
+                repo.get_seeded(seed, 0, false).await.unwrap().id(),
+            ));
+        }
+
+        assert!(ids.len() > 1);
+
+        let mut walk = HashSet::new();
+        for position in 0..32 {
+            walk.insert(i32::from(
+                repo.get_seeded(202_401, position, false)
+                    .await
+                    .unwrap()
+                    .id(),
+            ));
+        }
+
+        assert_eq!(walk.len(), 32);
+        assert_eq!(
+            repo.get_seeded(202_401, 32, false).await,
+            Err(GetRandomFactError::Empty)
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn sample(pool: PgPool) {
+        for _ in 0..32 {
+            let entity: SqlxFact = Faker.fake::<Fact>().into();
+
+            query!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2)",
+                entity.title,
+                entity.body,
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let repo = SqlxFactsRepository::new(pool);
+        let sample = repo.sample(8, 202_401).await.unwrap();
+
+        assert_eq!(sample.len(), 8);
+        assert_eq!(repo.sample(8, 202_401).await.unwrap(), sample);
+        assert_ne!(repo.sample(8, 202_402).await.unwrap(), sample);
+        assert_eq!(repo.sample(64, u64::MAX).await.unwrap().len(), 32);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_seeded_from_empty(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+
+        assert_eq!(
+            repo.get_seeded(202_401, 0, false).await,
+            Err(GetRandomFactError::Empty)
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_random_excluding(pool: PgPool) {
+        let mut ids = Vec::new();
+
+        for _ in 0..4 {
+            let fake = Faker.fake::<Fact>();
+            let entity: SqlxFact = fake.clone().into();
+
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                entity.title,
+                entity.body,
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
+        let repo = SqlxFactsRepository::new(pool);
+        let excluded = FactId::new(ids[0]).unwrap();
+
+        for _ in 0..32 {
+            let result = repo.get_random_excluding(excluded).await.unwrap();
+
+            assert_ne!(result.id(), excluded);
+            assert!(ids.contains(&i32::from(result.id())));
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_random_excluding_the_only_element(pool: PgPool) {
+        let fake = Faker.fake::<Fact>();
+        let entity: SqlxFact = fake.clone().into();
+
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            entity.title,
+            entity.body,
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqlxFactsRepository::new(pool);
+        let result = repo.get_random_excluding(FactId::new(id).unwrap()).await;
+
+        assert_eq!(result, Err(GetRandomFactError::Empty));
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list(pool: PgPool) {
+        let mut ids = Vec::new();
+
+        for day in 1..=8 {
+            let fake = Faker.fake::<Fact>();
+            let entity: SqlxFact = fake.clone().into();
+
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body, created_at) VALUES ($1, $2, $3) RETURNING id",
+                entity.title,
+                entity.body,
+                DateTime::UNIX_EPOCH + chrono::Days::new(day),
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
+        let repo = SqlxFactsRepository::new(pool);
+
+        let page = repo
+            .list(&ListFactsRequest::new(3, 2).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            page.iter()
+                .map(|fact| i32::from(fact.id()))
+                .collect::<Vec<_>>(),
+            ids[2..5]
+        );
+
+        let window = repo
+            .list(
+                &ListFactsRequest::default()
+                    .with_created_after(Some(DateTime::UNIX_EPOCH + chrono::Days::new(2)))
+                    .with_created_before(Some(DateTime::UNIX_EPOCH + chrono::Days::new(5))),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            window
+                .iter()
+                .map(|fact| i32::from(fact.id()))
+                .collect::<Vec<_>>(),
+            ids[2..4]
+        );
+
+        let inverted = repo
+            .list(
+                &ListFactsRequest::default()
+                    .with_created_after(Some(DateTime::UNIX_EPOCH + chrono::Days::new(5)))
+                    .with_created_before(Some(DateTime::UNIX_EPOCH + chrono::Days::new(2))),
+            )
+            .await
+            .unwrap();
+
+        assert!(inverted.is_empty());
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list_with_total(pool: PgPool) {
+        for day in 1..=5 {
+            let entity: SqlxFact = Faker.fake::<Fact>().into();
+
+            query!(
+                "INSERT INTO facts (title, body, created_at) VALUES ($1, $2, $3)",
+                entity.title,
+                entity.body,
+                DateTime::UNIX_EPOCH + chrono::Days::new(day),
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let repo = SqlxFactsRepository::new(pool);
+
+        for (offset, size) in [(0, 2), (2, 2), (4, 1), (10, 0)] {
+            let (page, total) = repo
+                .list_with_total(&ListFactsRequest::new(2, offset).unwrap())
+                .await
+                .unwrap();
+
+            assert_eq!(page.len(), size);
+            assert_eq!(total, 5);
+        }
+
+        let (page, total) = repo
+            .list_with_total(
+                &ListFactsRequest::new(1, 0)
+                    .unwrap()
+                    .with_created_after(Some(DateTime::UNIX_EPOCH + chrono::Days::new(2))),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(page.len(), 1);
+        assert_eq!(total, 3);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn create(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let mut id: i32 = 0;
+
+        for _ in 0..32 {
+            let fake = Faker.fake::<CreateFactRequest>();
+            let fact = repo.create(&fake).await.unwrap();
+
+            if id.ne(&0) {
+                assert_eq!(i32::from(fact.id()), id + 1);
+                id += 1;
+            } else {
+                id = i32::from(fact.id());
+            }
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn create_with_tags(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool.clone());
+        let tags = vec![
+            FactTag::new("geology").unwrap(),
+            FactTag::new("space").unwrap(),
+        ];
+
+        let fact = repo
+            .create(&Faker.fake::<CreateFactRequest>().with_tags(tags))
+            .await
+            .unwrap();
+
+        let stored = query_scalar!(
+            "SELECT tag FROM fact_tags WHERE fact_id = $1 ORDER BY tag",
+            i32::from(fact.id())
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(stored, vec!["geology".to_owned(), "space".to_owned()]);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn find(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let mut ids = Vec::new();
+
+        for (title, body) in [
+            ("About Smoking", "Smoking kills"),
+            ("About sleeping", "Sleep is good, unlike smoking"),
+            ("About 100% juice", "Still a lot of sugar"),
+        ] {
+            let request = CreateFactRequest::new(
+                &FactTitle::new(title).unwrap(),
+                &FactBody::new(body).unwrap(),
+            );
+            ids.push(repo.create(&request).await.unwrap().id());
+        }
+        repo.add_tag(ids[1], &FactTag::new("health").unwrap())
+            .await
+            .unwrap();
+
+        let find = async |request: FindFactsRequest| {
+            repo.find(&request)
+                .await
+                .unwrap()
+                .iter()
+                .map(Fact::id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            find(FindFactsRequest::new(Some("smok".to_owned()), None, None).unwrap()).await,
+            vec![ids[0]]
+        );
+        assert_eq!(
+            find(FindFactsRequest::new(None, Some("SMOKING".to_owned()), None).unwrap()).await,
+            vec![ids[0], ids[1]]
+        );
+        assert_eq!(
+            find(FindFactsRequest::new(Some("%".to_owned()), None, None).unwrap()).await,
+            vec![ids[2]]
+        );
+        assert_eq!(
+            find(
+                FindFactsRequest::new(
+                    None,
+                    Some("smoking".to_owned()),
+                    Some(FactTag::new("health").unwrap())
+                )
+                .unwrap()
+            )
+            .await,
+            vec![ids[1]]
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list_by_actor(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let mut ids = Vec::new();
+
+        for actor in ["alice", "bob", "alice"] {
+            let fake = Faker
+                .fake::<CreateFactRequest>()
+                .with_created_by(Some(actor.to_owned()));
+            ids.push(i32::from(repo.create(&fake).await.unwrap().id()));
+        }
+        repo.create(&Faker.fake::<CreateFactRequest>().with_created_by(None))
+            .await
+            .unwrap();
+
+        let alice = repo.list_by_actor("alice").await.unwrap();
+
+        assert_eq!(
+            alice
+                .iter()
+                .map(|fact| i32::from(fact.id()))
+                .collect::<Vec<_>>(),
+            vec![ids[0], ids[2]]
+        );
+
+        let bob = repo.list_by_actor("bob").await.unwrap();
+
+        assert_eq!(
+            bob.iter()
+                .map(|fact| i32::from(fact.id()))
+                .collect::<Vec<_>>(),
+            vec![ids[1]]
+        );
+
+        assert!(repo.list_by_actor("carol").await.unwrap().is_empty());
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list_by_actor_beyond_one_page(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let total = ListFactsRequest::MAX_LIMIT + 5;
+
+        for _ in 0..total {
+            let fake = Faker
+                .fake::<CreateFactRequest>()
+                .with_created_by(Some("alice".to_owned()));
+            repo.create(&fake).await.unwrap();
+        }
+
+        let alice = repo.list_by_actor("alice").await.unwrap();
+
+        assert_eq!(alice.len(), total as usize);
+        assert_eq!(
+            alice
+                .iter()
+                .map(|fact| i32::from(fact.id()))
+                .collect::<HashSet<_>>()
+                .len(),
+            total as usize
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn create_if_absent(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let fake = Faker.fake::<CreateFactRequest>();
+
+        let fact = repo.create_if_absent(&fake).await.unwrap();
+
+        assert_eq!(fact.title(), fake.title());
+        assert_eq!(
+            repo.create_if_absent(&fake).await,
+            Err(CreateFactError::TitleTaken {
+                title: fake.title().to_owned().into()
+            })
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn concurrent_create_if_absent(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let title: FactTitle = Faker.fake();
+
+        let creates = (0..10)
+            .map(|_| {
+                let repo = repo.clone();
+                let request = CreateFactRequest::new(&title, &Faker.fake());
+                tokio::spawn(async move { repo.create_if_absent(&request).await })
+            })
+            .collect::<Vec<_>>();
+        let mut results = Vec::new();
+        for create in creates {
+            results.push(create.await.unwrap());
+        }
+
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(results
+            .iter()
+            .filter_map(|result| result.as_ref().err())
+            .all(|err| *err
+                == CreateFactError::TitleTaken {
+                    title: title.clone().into()
+                }));
+        assert_eq!(repo.count(false).await.unwrap(), 1);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn start_ids_from(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+
+        repo.start_ids_from(FactId::new(1000).unwrap())
+            .await
+            .unwrap();
+
+        let fact = repo.create(&Faker.fake()).await.unwrap();
+
+        assert_eq!(i32::from(fact.id()), 1000);
+
+        repo.start_ids_from(FactId::new(10).unwrap()).await.unwrap();
+
+        let fact = repo.create(&Faker.fake()).await.unwrap();
+
+        assert_eq!(i32::from(fact.id()), 1001);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn resync_sequence(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool.clone());
+
+        assert_eq!(i32::from(repo.resync_sequence().await.unwrap()), 1);
+
+        let entity: SqlxFact = Faker.fake::<Fact>().into();
+        query!(
+            "INSERT INTO facts (id, title, body) VALUES ($1, $2, $3)",
+            500,
+            entity.title,
+            entity.body,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(i32::from(repo.resync_sequence().await.unwrap()), 501);
 
-    use super::*;
+        let fact = repo.create(&Faker.fake()).await.unwrap();
+
+        assert_eq!(i32::from(fact.id()), 501);
+    }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn get(pool: PgPool) {
-        let fake = Faker.fake::<Fact>();
-        let entity: SqlxFact = fake.clone().into();
+    async fn delete_non_existent(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let id = Faker.fake();
+        let result = repo.delete(id).await;
 
-        let id = query_scalar!(
-            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
-            entity.title,
-            entity.body,
-        )
-        .fetch_one(&pool)
-        .await
-        .unwrap();
+        assert_eq!(result, Err(DeleteFactError::NoSuchFact { id }));
+    }
 
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn update_non_existent(pool: PgPool) {
         let repo = SqlxFactsRepository::new(pool);
+        let id = Faker.fake();
+        let result = repo
+            .update(id, &Faker.fake(), &Faker.fake(), None, false)
+            .await;
 
-        let result: Fact = repo.get(FactId::new(id).unwrap()).await.unwrap();
+        assert_eq!(result, Err(UpdateFactError::NoSuchFact { id }));
+    }
 
-        assert_eq!(fake.body(), result.body());
-        assert_eq!(fake.title(), result.title());
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn update_tracks_actors(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let created = repo
+            .create(
+                &Faker
+                    .fake::<CreateFactRequest>()
+                    .with_created_by(Some("alice".to_owned())),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(created.created_by(), Some("alice"));
+        assert_eq!(created.updated_by(), None);
+
+        let updated = repo
+            .update(
+                created.id(),
+                &Faker.fake(),
+                &Faker.fake(),
+                Some("bob"),
+                false,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.created_by(), Some("alice"));
+        assert_eq!(updated.updated_by(), Some("bob"));
+        assert_eq!(repo.get(created.id(), false).await.unwrap(), updated);
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn get_non_existent(pool: PgPool) {
+    async fn concurrent_create_beyond_max_total(pool: PgPool) {
         let repo = SqlxFactsRepository::new(pool);
-        let id = Faker.fake();
-        let result = repo.get(id).await;
 
-        assert_eq!(result, Err(GetFactError::NoSuchFact { id }));
+        let creates = (0..10)
+            .map(|_| {
+                let repo = repo.clone();
+                tokio::spawn(async move {
+                    repo.create(&Faker.fake::<CreateFactRequest>().with_max_total(Some(3)))
+                        .await
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut results = Vec::new();
+        for create in creates {
+            results.push(create.await.unwrap());
+        }
+
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 3);
+        assert!(results
+            .iter()
+            .filter_map(|result| result.as_ref().err())
+            .all(|err| *err == CreateFactError::StorageFull { max: 3 }));
+        assert_eq!(repo.count(false).await.unwrap(), 3);
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn get_random_from_empty(pool: PgPool) {
+    async fn concurrent_create_duplicate_body(pool: PgPool) {
         let repo = SqlxFactsRepository::new(pool);
-        let result = repo.get_random().await;
+        let body: FactBody = Faker.fake();
 
-        assert_eq!(result, Err(GetRandomFactError::Empty));
+        let creates = (0..10)
+            .map(|_| {
+                let repo = repo.clone();
+                let request = CreateFactRequest::new(&Faker.fake(), &body).with_unique_body(true);
+                tokio::spawn(async move { repo.create(&request).await })
+            })
+            .collect::<Vec<_>>();
+        let mut results = Vec::new();
+        for create in creates {
+            results.push(create.await.unwrap());
+        }
+
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(results
+            .iter()
+            .filter_map(|result| result.as_ref().err())
+            .all(|err| *err == CreateFactError::BodyTaken));
+        assert_eq!(repo.count(false).await.unwrap(), 1);
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn get_random_from_one_element(pool: PgPool) {
+    async fn like_non_existent(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let id = Faker.fake();
+
+        assert_eq!(
+            repo.like(id, false).await,
+            Err(UpdateFactError::NoSuchFact { id })
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn concurrent_likes(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool.clone());
+        let entity: SqlxFact = Faker.fake::<Fact>().into();
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            entity.title,
+            entity.body,
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let id = FactId::new(id).unwrap();
+
+        assert_eq!(repo.like(id, false).await.unwrap(), 1);
+        assert_eq!(repo.like(id, false).await.unwrap(), 2);
+
+        let likes = (0..20)
+            .map(|_| {
+                let repo = repo.clone();
+                tokio::spawn(async move { repo.like(id, false).await.unwrap() })
+            })
+            .collect::<Vec<_>>();
+        let mut counts = Vec::new();
+        for like in likes {
+            counts.push(like.await.unwrap());
+        }
+        counts.sort_unstable();
+
+        assert_eq!(counts, (3..=22).collect::<Vec<_>>());
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn double_delete(pool: PgPool) {
         let fake = Faker.fake::<Fact>();
         let entity: SqlxFact = fake.clone().into();
 
-        query!(
-            "INSERT INTO facts (title, body) VALUES ($1, $2)",
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
             entity.title,
             entity.body,
         )
-        .execute(&pool)
+        .fetch_one(&pool)
         .await
         .unwrap();
 
         let repo = SqlxFactsRepository::new(pool);
 
-        let result = repo.get_random().await.unwrap();
+        repo.delete(FactId::new(id).unwrap()).await.unwrap();
 
-        assert_eq!(fake.title(), result.title());
-        assert_eq!(fake.body(), result.body());
+        assert!(matches!(
+            repo.delete(FactId::new(id).unwrap()).await,
+            Err(DeleteFactError::NoSuchFact { id: _ })
+        ));
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn get_random_from_one_corrupted_element(pool: PgPool) {
+    async fn add_tag(pool: PgPool) {
         let fake = Faker.fake::<Fact>();
         let entity: SqlxFact = fake.clone().into();
 
-        query!(
-            "INSERT INTO facts (id, title, body) VALUES ($1, $2, $3)",
-            0,
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
             entity.title,
             entity.body,
         )
-        .execute(&pool)
+        .fetch_one(&pool)
         .await
         .unwrap();
 
+        let repo = SqlxFactsRepository::new(pool.clone());
+        let tag = Faker.fake::<FactTag>();
+
+        repo.add_tag(FactId::new(id).unwrap(), &tag).await.unwrap();
+        repo.add_tag(FactId::new(id).unwrap(), &tag).await.unwrap();
+
+        let tags = query_scalar!("SELECT tag FROM fact_tags WHERE fact_id = $1", id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(tags, vec![String::from(tag)]);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn modified_since(pool: PgPool) {
+        let mut ids = Vec::new();
+
+        for day in 1..=3 {
+            let entity: SqlxFact = Faker.fake::<Fact>().into();
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body, created_at, updated_at) VALUES ($1, $2, $3, $3) RETURNING id",
+                entity.title,
+                entity.body,
+                DateTime::UNIX_EPOCH + chrono::Days::new(day),
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
         let repo = SqlxFactsRepository::new(pool);
+        let ids_since = |facts: Vec<Fact>| {
+            facts
+                .iter()
+                .map(|fact| i32::from(fact.id()))
+                .collect::<Vec<_>>()
+        };
 
-        assert!(matches!(
-            repo.get_random().await,
-            Err(GetRandomFactError::UnexpectedError { inner: _ })
-        ));
+        assert_eq!(
+            ids_since(
+                repo.modified_since(DateTime::UNIX_EPOCH + chrono::Days::new(1), false)
+                    .await
+                    .unwrap()
+            ),
+            ids[1..]
+        );
+
+        repo.add_tag(FactId::new(ids[0]).unwrap(), &Faker.fake())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ids_since(
+                repo.modified_since(DateTime::UNIX_EPOCH + chrono::Days::new(3), false)
+                    .await
+                    .unwrap()
+            ),
+            ids[..1]
+        );
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn get_random(pool: PgPool) {
-        for _ in 0..32 {
-            let fake = Faker.fake::<Fact>();
-            let entity: SqlxFact = fake.clone().into();
+    async fn recent(pool: PgPool) {
+        let now = Utc::now();
+        let mut ids = Vec::new();
 
-            query!(
-                "INSERT INTO facts (title, body) VALUES ($1, $2)",
+        for seconds in [10 * 60 + 5, 10 * 60 - 5, 30] {
+            let entity: SqlxFact = Faker.fake::<Fact>().into();
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body, created_at) VALUES ($1, $2, $3) RETURNING id",
                 entity.title,
                 entity.body,
+                now - chrono::TimeDelta::seconds(seconds),
             )
-            .execute(&pool)
+            .fetch_one(&pool)
             .await
             .unwrap();
+
+            ids.push(id);
         }
 
         let repo = SqlxFactsRepository::new(pool);
 
-        repo.get_random().await.unwrap();
+        for (minutes, expected) in [
+            (0, &ids[3..]),
+            (1, &ids[2..]),
+            (10, &ids[1..]),
+            (11, &ids[..]),
+        ] {
+            assert_eq!(
+                repo.recent(minutes, false)
+                    .await
+                    .unwrap()
+                    .iter()
+                    .map(|fact| i32::from(fact.id()))
+                    .collect::<Vec<_>>(),
+                expected,
+                "{minutes}"
+            );
+        }
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn create(pool: PgPool) {
+    async fn digest(pool: PgPool) {
         let repo = SqlxFactsRepository::new(pool);
-        let mut id: i32 = 0;
+        let empty = repo.digest(false).await.unwrap();
 
-        for _ in 0..32 {
-            let fake = Faker.fake::<CreateFactRequest>();
-            let fact = repo.create(&fake).await.unwrap();
+        assert_eq!(repo.digest(false).await.unwrap(), empty);
 
-            if id.ne(&0) {
-                assert_eq!(i32::from(fact.id()), id + 1);
-                id += 1;
-            } else {
-                id = i32::from(fact.id());
+        let fact = repo
+            .create(&CreateFactRequest::new(
+                &FactTitle::new("foo").unwrap(),
+                &FactBody::new("bar").unwrap(),
+            ))
+            .await
+            .unwrap();
+        let created = repo.digest(false).await.unwrap();
+
+        assert_ne!(created, empty);
+        assert_eq!(repo.digest(false).await.unwrap(), created);
+
+        repo.add_tag(fact.id(), &FactTag::new("science").unwrap())
+            .await
+            .unwrap();
+
+        assert_ne!(repo.digest(false).await.unwrap(), created);
+
+        let public = repo.digest(true).await.unwrap();
+        let private = repo
+            .create(&Faker.fake::<CreateFactRequest>().with_public(false))
+            .await
+            .unwrap();
+        repo.add_tag(private.id(), &FactTag::new("secret").unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(repo.digest(true).await.unwrap(), public);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn neighbors_and_count(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool);
+        let mut ids = Vec::new();
+
+        for (title, public) in [("first", true), ("second", false), ("third", true)] {
+            let request = CreateFactRequest::new(
+                &FactTitle::new(title).unwrap(),
+                &FactBody::new("body").unwrap(),
+            )
+            .with_public(public);
+            ids.push(repo.create(&request).await.unwrap().id());
+        }
+
+        assert_eq!(repo.count(false).await.unwrap(), 3);
+        assert_eq!(
+            repo.neighbors(ids[0], false).await.unwrap(),
+            (None, Some(ids[1]))
+        );
+        assert_eq!(
+            repo.neighbors(ids[1], false).await.unwrap(),
+            (Some(ids[0]), Some(ids[2]))
+        );
+        assert_eq!(
+            repo.neighbors(ids[2], false).await.unwrap(),
+            (Some(ids[1]), None)
+        );
+
+        assert_eq!(repo.count(true).await.unwrap(), 2);
+        assert_eq!(
+            repo.neighbors(ids[0], true).await.unwrap(),
+            (None, Some(ids[2]))
+        );
+        assert_eq!(
+            repo.neighbors(ids[2], true).await.unwrap(),
+            (Some(ids[0]), None)
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn count_by_tag(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool.clone());
+
+        assert_eq!(repo.count_by_tag(false).await.unwrap(), vec![]);
+
+        for (tags, public) in [
+            (vec!["health", "science"], true),
+            (vec!["health"], true),
+            (vec!["health", "history"], false),
+            (vec!["science"], true),
+        ] {
+            let entity: SqlxFact = Faker.fake::<Fact>().into();
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body, public) VALUES ($1, $2, $3) RETURNING id",
+                entity.title,
+                entity.body,
+                public,
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            for tag in tags {
+                repo.add_tag(FactId::new(id).unwrap(), &FactTag::new(tag).unwrap())
+                    .await
+                    .unwrap();
             }
         }
+
+        assert_eq!(
+            repo.count_by_tag(false).await.unwrap(),
+            vec![
+                (FactTag::new("health").unwrap(), 3),
+                (FactTag::new("science").unwrap(), 2),
+                (FactTag::new("history").unwrap(), 1),
+            ]
+        );
+        assert_eq!(
+            repo.count_by_tag(true).await.unwrap(),
+            vec![
+                (FactTag::new("health").unwrap(), 2),
+                (FactTag::new("science").unwrap(), 2),
+            ]
+        );
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn delete_non_existent(pool: PgPool) {
+    async fn count_by_day(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool.clone());
+
+        assert_eq!(repo.count_by_day(None, None, false).await.unwrap(), vec![]);
+
+        for (hours, public) in [(1, true), (5, true), (23, false), (25, true)] {
+            let entity: SqlxFact = Faker.fake::<Fact>().into();
+            query!(
+                "INSERT INTO facts (title, body, created_at, public) VALUES ($1, $2, $3, $4)",
+                entity.title,
+                entity.body,
+                DateTime::UNIX_EPOCH + chrono::TimeDelta::hours(hours),
+                public,
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let first = DateTime::UNIX_EPOCH.date_naive();
+        let second = first.succ_opt().unwrap();
+
+        assert_eq!(
+            repo.count_by_day(None, None, false).await.unwrap(),
+            vec![(first, 3), (second, 1)]
+        );
+        assert_eq!(
+            repo.count_by_day(None, None, true).await.unwrap(),
+            vec![(first, 2), (second, 1)]
+        );
+        assert_eq!(
+            repo.count_by_day(
+                Some(DateTime::UNIX_EPOCH + chrono::TimeDelta::hours(2)),
+                Some(DateTime::UNIX_EPOCH + chrono::TimeDelta::hours(24)),
+                false,
+            )
+            .await
+            .unwrap(),
+            vec![(first, 2)]
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list_by_tags(pool: PgPool) {
+        let repo = SqlxFactsRepository::new(pool.clone());
+        let mut ids = Vec::new();
+
+        for tags in [
+            vec!["health", "science"],
+            vec!["health"],
+            vec!["science"],
+            vec![],
+        ] {
+            let entity: SqlxFact = Faker.fake::<Fact>().into();
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                entity.title,
+                entity.body,
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            for tag in tags {
+                repo.add_tag(FactId::new(id).unwrap(), &FactTag::new(tag).unwrap())
+                    .await
+                    .unwrap();
+            }
+
+            ids.push(id);
+        }
+
+        let tags = [
+            FactTag::new("health").unwrap(),
+            FactTag::new("science").unwrap(),
+        ];
+        let matching = |mode| {
+            let repo = repo.clone();
+            let tags = tags.clone();
+            async move {
+                repo.list_by_tags(&tags, mode, false)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|fact| i32::from(fact.id()))
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(matching(TagMatch::All).await, vec![ids[0]]);
+        assert_eq!(matching(TagMatch::Any).await, ids[..3]);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn add_tag_to_non_existent(pool: PgPool) {
         let repo = SqlxFactsRepository::new(pool);
         let id = Faker.fake();
-        let result = repo.delete(id).await;
+        let result = repo.add_tag(id, &Faker.fake()).await;
 
-        assert_eq!(result, Err(DeleteFactError::NoSuchFact { id }));
+        assert_eq!(result, Err(AddFactTagError::NoSuchFact { id }));
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn double_delete(pool: PgPool) {
+    async fn remove_tag(pool: PgPool) {
         let fake = Faker.fake::<Fact>();
         let entity: SqlxFact = fake.clone().into();
 
@@ -402,12 +2980,41 @@ mod tests {
         .unwrap();
 
         let repo = SqlxFactsRepository::new(pool);
+        let id = FactId::new(id).unwrap();
+        let tag = Faker.fake::<FactTag>();
 
-        repo.delete(FactId::new(id).unwrap()).await.unwrap();
+        repo.add_tag(id, &tag).await.unwrap();
+        repo.remove_tag(id, &tag).await.unwrap();
 
-        assert!(matches!(
-            repo.delete(FactId::new(id).unwrap()).await,
-            Err(DeleteFactError::NoSuchFact { id: _ })
-        ));
+        assert_eq!(
+            repo.remove_tag(id, &tag).await,
+            Err(RemoveFactTagError::NoSuchTag { id, tag })
+        );
+    }
+
+    #[sqlx::test(migrations = "./src/facts/migrations")]
+    async fn migration_status(pool: PgPool) {
+        let versions = MIGRATOR
+            .iter()
+            .map(|migration| migration.version)
+            .collect::<Vec<_>>();
+        let repo = SqlxFactsRepository::new(pool.clone());
+
+        assert_eq!(
+            repo.migration_status().await.unwrap(),
+            MigrationStatus::new(versions.clone(), Vec::new())
+        );
+
+        let (last, applied) = versions.split_last().unwrap();
+        sqlx::query("DELETE FROM _sqlx_migrations WHERE version = $1")
+            .bind(last)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repo.migration_status().await.unwrap(),
+            MigrationStatus::new(applied.to_vec(), vec![*last])
+        );
     }
 }
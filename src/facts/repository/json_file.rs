@@ -0,0 +1,1323 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+    fs::{File, OpenOptions},
+    io::ErrorKind,
+    ops::{Bound, Deref, DerefMut},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        PoisonError,
+        Weak,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use fs4::{FileExt, TryLockError};
+use rand::seq::{IndexedRandom, IteratorRandom};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::{
+    fs,
+    sync::{Notify, RwLock},
+    task::spawn_blocking,
+    time::sleep,
+};
+use tracing::error;
+
+use super::{
+    errors::{
+        AddFactTagError,
+        CreateFactError,
+        DeleteFactError,
+        GetExtremesError,
+        GetFactError,
+        GetRandomFactError,
+        ListFactsError,
+        MigrationStatusError,
+        RemoveFactTagError,
+        ResyncSequenceError,
+        UpdateFactError,
+    },
+    models::{
+        CreateFactRequest,
+        Fact,
+        FactBody,
+        FactError,
+        FactId,
+        FactTag,
+        FactTagError,
+        FactTitle,
+        FactsDigest,
+        FindFactsRequest,
+        InvalidFact,
+        ListFactsRequest,
+        MigrationStatus,
+        TagMatch,
+    },
+    FactsRepository,
+};
+
+#[derive(Clone, Serialize, Deserialize)]
+struct JsonFact {
+    id: i32,
+    title: String,
+    body: String,
+    created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_by: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    tags: BTreeSet<String>,
+    #[serde(default = "public_by_default")]
+    public: bool,
+    #[serde(default)]
+    likes: u64,
+}
+
+fn public_by_default() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredFacts {
+    Counted { next_id: i32, facts: Vec<JsonFact> },
+    Legacy(Vec<JsonFact>),
+}
+
+#[derive(Serialize)]
+struct StoredFactsRef<'a> {
+    next_id: i32,
+    facts: Vec<&'a JsonFact>,
+}
+
+struct JsonFacts {
+    facts: BTreeMap<i32, JsonFact>,
+    next_id: i32,
+}
+
+impl From<StoredFacts> for JsonFacts {
+    fn from(value: StoredFacts) -> Self {
+        let (next_id, facts) = match value {
+            StoredFacts::Counted { next_id, facts } => (next_id, facts),
+            StoredFacts::Legacy(facts) => (1, facts),
+        };
+        let facts = facts
+            .into_iter()
+            .map(|fact| (fact.id, fact))
+            .collect::<BTreeMap<_, _>>();
+        let next_id = facts
+            .last_key_value()
+            .map_or(next_id, |(id, _)| next_id.max(id + 1));
+
+        Self { facts, next_id }
+    }
+}
+
+impl Deref for JsonFacts {
+    type Target = BTreeMap<i32, JsonFact>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.facts
+    }
+}
+
+impl DerefMut for JsonFacts {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.facts
+    }
+}
+
+impl TryFrom<&JsonFact> for Fact {
+    type Error = FactError;
+
+    fn try_from(value: &JsonFact) -> Result<Self, Self::Error> {
+        Ok(Fact::new(
+            FactId::new(value.id)?,
+            &FactTitle::new(&value.title)?,
+            &FactBody::new(&value.body)?,
+            value.created_at,
+        )
+        .with_actors(value.created_by.clone(), value.updated_by.clone()))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum JsonFileError {
+    #[error("File {path:?} is locked by another process")]
+    Locked { path: PathBuf },
+    #[error("Cannot access {path:?}: {inner}")]
+    Io { path: PathBuf, inner: String },
+    #[error("Cannot parse {path:?}: {inner}")]
+    InvalidContent { path: PathBuf, inner: String },
+}
+
+struct Inner {
+    path: PathBuf,
+    facts: RwLock<JsonFacts>,
+    dirty: AtomicBool,
+    writing: std::sync::Mutex<()>,
+    _lock: File,
+}
+
+impl Inner {
+    // Blocking on purpose: it also runs from `Drop`, where nothing can be awaited.
+    fn persist(&self, facts: &JsonFacts) -> Result<(), JsonFileError> {
+        let _writing = self.writing.lock().unwrap_or_else(PoisonError::into_inner);
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
+
+        // Clearing the flag up front keeps changes made while writing dirty,
+        // so a failed write has to put it back for the next attempt.
+        let written = self.write(facts);
+        if written.is_err() {
+            self.dirty.store(true, Ordering::Release);
+        }
+
+        written
+    }
+
+    fn write(&self, facts: &JsonFacts) -> Result<(), JsonFileError> {
+        let content = serde_json::to_vec_pretty(&StoredFactsRef {
+            next_id: facts.next_id,
+            facts: facts.values().collect(),
+        })
+        .map_err(|err| JsonFileError::InvalidContent {
+            path: self.path.clone(),
+            inner: err.to_string(),
+        })?;
+
+        let temporary = with_suffix(&self.path, ".tmp");
+        std::fs::write(&temporary, content).map_err(|err| JsonFileError::Io {
+            path: temporary.clone(),
+            inner: err.to_string(),
+        })?;
+        std::fs::rename(&temporary, &self.path).map_err(|err| JsonFileError::Io {
+            path: self.path.clone(),
+            inner: err.to_string(),
+        })
+    }
+}
+
+pub struct JsonFileFactsRepository {
+    inner: Arc<Inner>,
+    changed: Arc<Notify>,
+}
+
+fn shuffle_key(seed: i64, id: i32) -> u64 {
+    let mut key = seed.cast_unsigned() ^ u64::from(id.cast_unsigned());
+
+    key = (key ^ (key >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    key = (key ^ (key >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    key ^ (key >> 31)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn recency_weight(created_at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    let age_hours = (now - created_at).num_seconds().max(0) as f64 / 3600.0;
+
+    1.0 / (1.0 + age_hours)
+}
+
+fn matches_tags(fact: &JsonFact, tags: &[FactTag], mode: TagMatch) -> bool {
+    let tagged = |tag: &FactTag| fact.tags.contains(&tag.to_string());
+
+    match mode {
+        TagMatch::Any => tags.iter().any(tagged),
+        TagMatch::All => tags.iter().all(tagged),
+    }
+}
+
+fn matches_list_request(fact: &JsonFact, request: &ListFactsRequest) -> bool {
+    request
+        .created_after()
+        .is_none_or(|after| fact.created_at > after)
+        && request
+            .created_before()
+            .is_none_or(|before| fact.created_at < before)
+        && request
+            .created_by()
+            .is_none_or(|actor| fact.created_by.as_deref() == Some(actor))
+        && (request.tags().is_empty() || matches_tags(fact, request.tags(), request.tag_match()))
+        && (fact.public || !request.public_only())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut raw = path.as_os_str().to_owned();
+    raw.push(suffix);
+    raw.into()
+}
+
+impl JsonFileFactsRepository {
+    pub async fn open(path: &Path, debounce: Duration) -> Result<Self, JsonFileError> {
+        let lock_path = with_suffix(path, ".lock");
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .map_err(|err| JsonFileError::Io {
+                path: lock_path.clone(),
+                inner: err.to_string(),
+            })?;
+        FileExt::try_lock(&lock).map_err(|err| match err {
+            TryLockError::WouldBlock => JsonFileError::Locked {
+                path: path.to_owned(),
+            },
+            TryLockError::Error(err) => JsonFileError::Io {
+                path: lock_path,
+                inner: err.to_string(),
+            },
+        })?;
+
+        let facts: StoredFacts = match fs::read(path).await {
+            Ok(content) => {
+                serde_json::from_slice(&content).map_err(|err| JsonFileError::InvalidContent {
+                    path: path.to_owned(),
+                    inner: err.to_string(),
+                })?
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => StoredFacts::Legacy(Vec::new()),
+            Err(err) => {
+                return Err(JsonFileError::Io {
+                    path: path.to_owned(),
+                    inner: err.to_string(),
+                })
+            }
+        };
+
+        let inner = Arc::new(Inner {
+            path: path.to_owned(),
+            facts: RwLock::new(facts.into()),
+            dirty: AtomicBool::new(false),
+            writing: std::sync::Mutex::default(),
+            _lock: lock,
+        });
+        let changed = Arc::new(Notify::new());
+
+        tokio::spawn(Self::persist_changes(
+            Arc::downgrade(&inner),
+            changed.clone(),
+            debounce,
+        ));
+
+        Ok(Self { inner, changed })
+    }
+
+    async fn persist_changes(inner: Weak<Inner>, changed: Arc<Notify>, debounce: Duration) {
+        loop {
+            changed.notified().await;
+            sleep(debounce).await;
+
+            let Some(inner) = inner.upgrade() else {
+                break;
+            };
+            let path = inner.path.clone();
+            let persisted = spawn_blocking(move || inner.persist(&inner.facts.blocking_read()))
+                .await
+                .unwrap_or_else(|err| {
+                    Err(JsonFileError::Io {
+                        path,
+                        inner: err.to_string(),
+                    })
+                });
+            if let Err(err) = persisted {
+                error!("Cannot persist facts: {err}");
+            }
+        }
+    }
+
+    fn mark_changed(&self) {
+        self.inner.dirty.store(true, Ordering::Release);
+        self.changed.notify_one();
+    }
+
+    fn insert(
+        &self,
+        facts: &mut JsonFacts,
+        data: &CreateFactRequest,
+    ) -> Result<Fact, CreateFactError> {
+        let body = String::from(data.body().to_owned());
+        if data.unique_body() && facts.values().any(|fact| fact.body == body) {
+            return Err(CreateFactError::BodyTaken);
+        }
+
+        if let Some(max) = data.max_total() {
+            if facts.len() as u64 >= max {
+                return Err(CreateFactError::StorageFull { max });
+            }
+        }
+
+        let id = facts.next_id;
+        let fact = JsonFact {
+            id,
+            title: data.title().to_owned().into(),
+            body,
+            created_at: Utc::now(),
+            updated_at: None,
+            created_by: data.created_by().map(ToOwned::to_owned),
+            updated_by: None,
+            tags: data.tags().iter().map(ToString::to_string).collect(),
+            public: data.public(),
+            likes: 0,
+        };
+        let result =
+            (&fact)
+                .try_into()
+                .map_err(|err: FactError| CreateFactError::UnexpectedError {
+                    inner: err.to_string(),
+                })?;
+
+        facts.insert(id, fact);
+        facts.next_id = id + 1;
+        self.mark_changed();
+
+        Ok(result)
+    }
+
+    #[cfg(test)]
+    pub async fn flush(&self) -> Result<(), JsonFileError> {
+        self.inner.persist(&*self.inner.facts.read().await)
+    }
+}
+
+impl Drop for JsonFileFactsRepository {
+    fn drop(&mut self) {
+        // The persister only holds a weak handle and would skip a pending
+        // debounced write, so the last changes are written out here.
+        let Ok(facts) = self.inner.facts.try_read() else {
+            error!("Cannot persist facts: the store is still locked");
+            return;
+        };
+        if let Err(err) = self.inner.persist(&facts) {
+            error!("Cannot persist facts: {err}");
+        }
+    }
+}
+
+#[async_trait]
+impl FactsRepository for JsonFileFactsRepository {
+    async fn get(&self, id: FactId, public_only: bool) -> Result<Fact, GetFactError> {
+        let facts = self.inner.facts.read().await;
+
+        facts
+            .get(&i32::from(id))
+            .filter(|fact| fact.public || !public_only)
+            .ok_or(GetFactError::NoSuchFact { id })?
+            .try_into()
+            .map_err(|err: FactError| GetFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn get_random(&self) -> Result<Fact, GetRandomFactError> {
+        let facts = self.inner.facts.read().await;
+        let fact = facts
+            .values()
+            .choose(&mut rand::rng())
+            .ok_or(GetRandomFactError::Empty)?;
+
+        fact.try_into()
+            .map_err(|err: FactError| GetRandomFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn get_random_short(&self, max_body_len: usize) -> Result<Fact, GetRandomFactError> {
+        let facts = self.inner.facts.read().await;
+        let fact = facts
+            .values()
+            .filter(|fact| fact.body.chars().count() <= max_body_len)
+            .choose(&mut rand::rng())
+            .ok_or(GetRandomFactError::Empty)?;
+
+        fact.try_into()
+            .map_err(|err: FactError| GetRandomFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn get_random_public(&self, max_body_len: usize) -> Result<Fact, GetRandomFactError> {
+        let facts = self.inner.facts.read().await;
+        let fact = facts
+            .values()
+            .filter(|fact| fact.public && fact.body.chars().count() <= max_body_len)
+            .choose(&mut rand::rng())
+            .ok_or(GetRandomFactError::Empty)?;
+
+        fact.try_into()
+            .map_err(|err: FactError| GetRandomFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn get_random_recent(
+        &self,
+        max_body_len: usize,
+        public_only: bool,
+    ) -> Result<Fact, GetRandomFactError> {
+        let facts = self.inner.facts.read().await;
+        let now = Utc::now();
+        let candidates = facts
+            .values()
+            .filter(|fact| {
+                (fact.public || !public_only) && fact.body.chars().count() <= max_body_len
+            })
+            .collect::<Vec<_>>();
+        let fact = candidates
+            .choose_weighted(&mut rand::rng(), |fact| {
+                recency_weight(fact.created_at, now)
+            })
+            .map_err(|_| GetRandomFactError::Empty)?;
+
+        (*fact)
+            .try_into()
+            .map_err(|err: FactError| GetRandomFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn get_random_excluding(&self, id: FactId) -> Result<Fact, GetRandomFactError> {
+        let facts = self.inner.facts.read().await;
+        let fact = facts
+            .values()
+            .filter(|fact| fact.id != i32::from(id))
+            .choose(&mut rand::rng())
+            .ok_or(GetRandomFactError::Empty)?;
+
+        fact.try_into()
+            .map_err(|err: FactError| GetRandomFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn get_seeded(
+        &self,
+        seed: i64,
+        position: u32,
+        public_only: bool,
+    ) -> Result<Fact, GetRandomFactError> {
+        let facts = self.inner.facts.read().await;
+        let mut shuffled = facts
+            .values()
+            .filter(|fact| fact.public || !public_only)
+            .collect::<Vec<_>>();
+        shuffled.sort_by_key(|fact| (shuffle_key(seed, fact.id), fact.id));
+
+        let fact = shuffled
+            .get(position as usize)
+            .ok_or(GetRandomFactError::Empty)?;
+
+        (*fact)
+            .try_into()
+            .map_err(|err: FactError| GetRandomFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn list(&self, request: &ListFactsRequest) -> Result<Vec<Fact>, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+
+        facts
+            .values()
+            .filter(|fact| matches_list_request(fact, request))
+            .skip(request.offset() as usize)
+            .take(request.limit() as usize)
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn list_with_total(
+        &self,
+        request: &ListFactsRequest,
+    ) -> Result<(Vec<Fact>, u64), ListFactsError> {
+        let facts = self.inner.facts.read().await;
+        let matching = facts
+            .values()
+            .filter(|fact| matches_list_request(fact, request))
+            .collect::<Vec<_>>();
+
+        let page = matching
+            .iter()
+            .skip(request.offset() as usize)
+            .take(request.limit() as usize)
+            .map(|fact| (*fact).try_into())
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })?;
+
+        Ok((page, matching.len() as u64))
+    }
+
+    async fn find(&self, request: &FindFactsRequest) -> Result<Vec<Fact>, ListFactsError> {
+        let contains = |haystack: &str, needle: Option<&str>| {
+            needle.is_none_or(|needle| haystack.to_lowercase().contains(&needle.to_lowercase()))
+        };
+        let facts = self.inner.facts.read().await;
+
+        facts
+            .values()
+            .filter(|fact| {
+                (fact.public || !request.public_only())
+                    && contains(&fact.title, request.title_contains())
+                    && contains(&fact.body, request.body_contains())
+                    && request
+                        .tag()
+                        .is_none_or(|tag| fact.tags.contains(&tag.to_string()))
+            })
+            .take(request.limit() as usize)
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn list_after(
+        &self,
+        after: Option<FactId>,
+        limit: u32,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+        let after = after.map_or(Bound::Unbounded, |id| Bound::Excluded(i32::from(id)));
+
+        facts
+            .range((after, Bound::Unbounded))
+            .take(limit as usize)
+            .map(|(_, fact)| fact.try_into())
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn sample(&self, n: u32, seed: u64) -> Result<Vec<Fact>, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+        let mut shuffled = facts.values().collect::<Vec<_>>();
+        shuffled.sort_by_key(|fact| (shuffle_key(seed.cast_signed(), fact.id), fact.id));
+
+        shuffled
+            .into_iter()
+            .take(n as usize)
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn latest(&self, limit: u32, public_only: bool) -> Result<Vec<Fact>, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+        let mut latest = facts
+            .values()
+            .filter(|fact| fact.public || !public_only)
+            .collect::<Vec<_>>();
+        latest.sort_by_key(|fact| Reverse((fact.created_at, fact.id)));
+
+        latest
+            .into_iter()
+            .take(limit as usize)
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn latest_tagged(
+        &self,
+        tag: &FactTag,
+        limit: u32,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+        let mut latest = facts
+            .values()
+            .filter(|fact| (fact.public || !public_only) && fact.tags.contains(&tag.to_string()))
+            .collect::<Vec<_>>();
+        latest.sort_by_key(|fact| Reverse((fact.created_at, fact.id)));
+
+        latest
+            .into_iter()
+            .take(limit as usize)
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn list_by_tags(
+        &self,
+        tags: &[FactTag],
+        mode: TagMatch,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+
+        facts
+            .values()
+            .filter(|fact| (fact.public || !public_only) && matches_tags(fact, tags, mode))
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn modified_since(
+        &self,
+        since: DateTime<Utc>,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+        let mut modified = facts
+            .values()
+            .filter(|fact| {
+                (fact.public || !public_only) && fact.updated_at.unwrap_or(fact.created_at) > since
+            })
+            .collect::<Vec<_>>();
+        modified.sort_by_key(|fact| (fact.updated_at.unwrap_or(fact.created_at), fact.id));
+
+        modified
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn recent(&self, minutes: u32, public_only: bool) -> Result<Vec<Fact>, ListFactsError> {
+        let since = Utc::now() - TimeDelta::minutes(i64::from(minutes));
+        let facts = self.inner.facts.read().await;
+        let mut recent = facts
+            .values()
+            .filter(|fact| (fact.public || !public_only) && fact.created_at > since)
+            .collect::<Vec<_>>();
+        recent.sort_by_key(|fact| (fact.created_at, fact.id));
+
+        recent
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .map_err(|err: FactError| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn count(&self, public_only: bool) -> Result<u64, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+
+        Ok(facts
+            .values()
+            .filter(|fact| fact.public || !public_only)
+            .count() as u64)
+    }
+
+    async fn extremes(&self, public_only: bool) -> Result<(Fact, Fact), GetExtremesError> {
+        let facts = self.inner.facts.read().await;
+        let length = |fact: &&JsonFact| fact.body.chars().count();
+        let visible = || facts.values().filter(|fact| fact.public || !public_only);
+        let longest = visible()
+            .max_by_key(|fact| (length(fact), Reverse(fact.id)))
+            .ok_or(GetExtremesError::Empty)?;
+        let shortest = visible()
+            .min_by_key(|fact| (length(fact), fact.id))
+            .ok_or(GetExtremesError::Empty)?;
+        let into_fact = |fact: &JsonFact| {
+            fact.try_into()
+                .map_err(|err: FactError| GetExtremesError::UnexpectedError {
+                    inner: err.to_string(),
+                })
+        };
+
+        Ok((into_fact(longest)?, into_fact(shortest)?))
+    }
+
+    async fn neighbors(
+        &self,
+        id: FactId,
+        public_only: bool,
+    ) -> Result<(Option<FactId>, Option<FactId>), ListFactsError> {
+        let facts = self.inner.facts.read().await;
+        let id = i32::from(id);
+        let neighbor = |id: Option<&i32>| {
+            id.copied().map(FactId::new).transpose().map_err(|err| {
+                ListFactsError::UnexpectedError {
+                    inner: err.to_string(),
+                }
+            })
+        };
+
+        let visible = |(_, fact): &(&i32, &JsonFact)| fact.public || !public_only;
+
+        Ok((
+            neighbor(facts.range(..id).rev().find(visible).map(|(id, _)| id))?,
+            neighbor(
+                facts
+                    .range(id.saturating_add(1)..)
+                    .find(visible)
+                    .map(|(id, _)| id),
+            )?,
+        ))
+    }
+
+    async fn count_by_tag(&self, public_only: bool) -> Result<Vec<(FactTag, u64)>, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+        let mut counts = BTreeMap::<&str, u64>::new();
+        for tag in facts
+            .values()
+            .filter(|fact| fact.public || !public_only)
+            .flat_map(|fact| &fact.tags)
+        {
+            *counts.entry(tag).or_default() += 1;
+        }
+
+        let mut counts = counts
+            .into_iter()
+            .map(|(tag, count)| Ok((FactTag::new(tag)?, count)))
+            .collect::<Result<Vec<_>, FactTagError>>()
+            .map_err(|err| ListFactsError::UnexpectedError {
+                inner: err.to_string(),
+            })?;
+        counts.sort_by_key(|(_, count)| Reverse(*count));
+
+        Ok(counts)
+    }
+
+    async fn count_by_day(
+        &self,
+        after: Option<DateTime<Utc>>,
+        before: Option<DateTime<Utc>>,
+        public_only: bool,
+    ) -> Result<Vec<(NaiveDate, u64)>, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+        let mut counts = BTreeMap::<NaiveDate, u64>::new();
+        for fact in facts.values().filter(|fact| {
+            (fact.public || !public_only)
+                && after.is_none_or(|after| fact.created_at > after)
+                && before.is_none_or(|before| fact.created_at < before)
+        }) {
+            *counts.entry(fact.created_at.date_naive()).or_default() += 1;
+        }
+
+        Ok(counts.into_iter().collect())
+    }
+
+    async fn digest(&self, public_only: bool) -> Result<String, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+        let mut digest = FactsDigest::default();
+        for fact in facts.values().filter(|fact| fact.public || !public_only) {
+            digest.update(fact.id, fact.updated_at.unwrap_or(fact.created_at));
+        }
+
+        Ok(digest.finish())
+    }
+
+    async fn create(&self, data: &CreateFactRequest) -> Result<Fact, CreateFactError> {
+        let mut facts = self.inner.facts.write().await;
+
+        self.insert(&mut facts, data)
+    }
+
+    async fn create_if_absent(&self, data: &CreateFactRequest) -> Result<Fact, CreateFactError> {
+        let mut facts = self.inner.facts.write().await;
+        let title = String::from(data.title().to_owned());
+
+        if facts.values().any(|fact| fact.title == title) {
+            return Err(CreateFactError::TitleTaken { title });
+        }
+
+        self.insert(&mut facts, data)
+    }
+
+    async fn update(
+        &self,
+        id: FactId,
+        title: &FactTitle,
+        body: &FactBody,
+        updated_by: Option<&str>,
+        unique_body: bool,
+    ) -> Result<Fact, UpdateFactError> {
+        let mut facts = self.inner.facts.write().await;
+        let raw_body = String::from(body.to_owned());
+        if unique_body
+            && facts
+                .values()
+                .any(|fact| fact.id != i32::from(id) && fact.body == raw_body)
+        {
+            return Err(UpdateFactError::BodyTaken);
+        }
+
+        let fact = facts
+            .get_mut(&i32::from(id))
+            .ok_or(UpdateFactError::NoSuchFact { id })?;
+
+        fact.title = title.to_owned().into();
+        fact.body = raw_body;
+        fact.updated_at = Some(Utc::now());
+        fact.updated_by = updated_by.map(ToOwned::to_owned);
+        self.mark_changed();
+
+        (&*fact)
+            .try_into()
+            .map_err(|err: FactError| UpdateFactError::UnexpectedError {
+                inner: err.to_string(),
+            })
+    }
+
+    async fn like(&self, id: FactId, public_only: bool) -> Result<u64, UpdateFactError> {
+        let mut facts = self.inner.facts.write().await;
+        let fact = facts
+            .get_mut(&i32::from(id))
+            .filter(|fact| fact.public || !public_only)
+            .ok_or(UpdateFactError::NoSuchFact { id })?;
+
+        fact.likes += 1;
+        self.mark_changed();
+
+        Ok(fact.likes)
+    }
+
+    async fn delete(&self, id: FactId) -> Result<(), DeleteFactError> {
+        self.inner
+            .facts
+            .write()
+            .await
+            .remove(&i32::from(id))
+            .ok_or(DeleteFactError::NoSuchFact { id })?;
+        self.mark_changed();
+
+        Ok(())
+    }
+
+    async fn add_tag(&self, id: FactId, tag: &FactTag) -> Result<(), AddFactTagError> {
+        let mut facts = self.inner.facts.write().await;
+        let fact = facts
+            .get_mut(&i32::from(id))
+            .ok_or(AddFactTagError::NoSuchFact { id })?;
+
+        if fact.tags.insert(tag.to_string()) {
+            fact.updated_at = Some(Utc::now());
+        }
+        self.mark_changed();
+
+        Ok(())
+    }
+
+    async fn remove_tag(&self, id: FactId, tag: &FactTag) -> Result<(), RemoveFactTagError> {
+        let removed = self
+            .inner
+            .facts
+            .write()
+            .await
+            .get_mut(&i32::from(id))
+            .filter(|fact| fact.tags.contains(&tag.to_string()))
+            .map(|fact| {
+                fact.tags.remove(&tag.to_string());
+                fact.updated_at = Some(Utc::now());
+            })
+            .is_some();
+
+        if !removed {
+            return Err(RemoveFactTagError::NoSuchTag {
+                id,
+                tag: tag.to_owned(),
+            });
+        }
+        self.mark_changed();
+
+        Ok(())
+    }
+
+    async fn migration_status(&self) -> Result<MigrationStatus, MigrationStatusError> {
+        Ok(MigrationStatus::default())
+    }
+
+    async fn resync_sequence(&self) -> Result<FactId, ResyncSequenceError> {
+        let facts = self.inner.facts.read().await;
+
+        FactId::new(facts.next_id).map_err(|err| ResyncSequenceError::UnexpectedError {
+            inner: err.to_string(),
+        })
+    }
+
+    async fn validate_all(&self) -> Result<Vec<InvalidFact>, ListFactsError> {
+        let facts = self.inner.facts.read().await;
+
+        Ok(facts
+            .values()
+            .filter_map(|fact| InvalidFact::check(fact.id, &fact.title, &fact.body))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use fake::{Fake, Faker};
+
+    use super::*;
+
+    fn temporary_path() -> PathBuf {
+        temp_dir().join(format!("facts-{}.json", Faker.fake::<u64>()))
+    }
+
+    fn cleanup(path: &Path) {
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_file(with_suffix(path, ".lock"));
+    }
+
+    #[tokio::test]
+    async fn random_recent_prefers_new_facts() {
+        let path = temporary_path();
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        let recent = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap();
+        let old = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap();
+        repo.inner
+            .facts
+            .write()
+            .await
+            .get_mut(&i32::from(old.id()))
+            .unwrap()
+            .created_at -= TimeDelta::hours(3);
+
+        let mut hits = 0;
+        for _ in 0..500 {
+            if repo
+                .get_random_recent(usize::MAX, false)
+                .await
+                .unwrap()
+                .id()
+                == recent.id()
+            {
+                hits += 1;
+            }
+        }
+
+        assert!((360..=440).contains(&hits), "{hits}");
+
+        drop(repo);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn recent_facts_within_window() {
+        let path = temporary_path();
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        let mut ids = Vec::new();
+        for seconds in [10 * 60 + 5, 10 * 60 - 5] {
+            let fact = repo
+                .create(&Faker.fake::<CreateFactRequest>())
+                .await
+                .unwrap();
+            repo.inner
+                .facts
+                .write()
+                .await
+                .get_mut(&i32::from(fact.id()))
+                .unwrap()
+                .created_at -= TimeDelta::seconds(seconds);
+            ids.push(fact.id());
+        }
+
+        let recent = |facts: Vec<Fact>| facts.iter().map(Fact::id).collect::<Vec<_>>();
+
+        assert_eq!(recent(repo.recent(9, false).await.unwrap()), []);
+        assert_eq!(recent(repo.recent(10, false).await.unwrap()), ids[1..]);
+        assert_eq!(recent(repo.recent(11, false).await.unwrap()), ids);
+
+        drop(repo);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn created_fact_survives_reload() {
+        let path = temporary_path();
+
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        let fact = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap();
+        repo.flush().await.unwrap();
+        drop(repo);
+
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+
+        assert_eq!(repo.get(fact.id(), false).await.unwrap(), fact);
+
+        drop(repo);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn pending_changes_persisted_on_drop() {
+        let path = temporary_path();
+
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        let fact = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap();
+        drop(repo);
+
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+
+        assert_eq!(repo.get(fact.id(), false).await.unwrap(), fact);
+
+        drop(repo);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn failed_persist_stays_dirty() {
+        let path = temporary_path();
+        let temporary = with_suffix(&path, ".tmp");
+
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        let fact = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap();
+
+        std::fs::create_dir(&temporary).unwrap();
+        let inner = repo.inner.clone();
+        let failed = spawn_blocking(move || inner.persist(&inner.facts.blocking_read()))
+            .await
+            .unwrap();
+        std::fs::remove_dir(&temporary).unwrap();
+
+        assert!(failed.is_err());
+        assert!(repo.inner.dirty.load(Ordering::Acquire));
+
+        drop(repo);
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+
+        assert_eq!(repo.get(fact.id(), false).await.unwrap(), fact);
+
+        drop(repo);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn deleted_ids_not_reused() {
+        let path = temporary_path();
+
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        let deleted = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap()
+            .id();
+        repo.delete(deleted).await.unwrap();
+        let created = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap()
+            .id();
+        repo.delete(created).await.unwrap();
+        drop(repo);
+
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        let reopened = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap()
+            .id();
+
+        assert!(i32::from(deleted) < i32::from(created));
+        assert!(i32::from(created) < i32::from(reopened));
+        assert_eq!(
+            repo.resync_sequence().await.unwrap(),
+            FactId::new(i32::from(reopened) + 1).unwrap()
+        );
+
+        drop(repo);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn update_duplicate_body() {
+        let path = temporary_path();
+
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        let first = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap();
+        let second = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repo.update(second.id(), second.title(), first.body(), None, true)
+                .await,
+            Err(UpdateFactError::BodyTaken)
+        );
+        assert!(repo
+            .update(first.id(), first.title(), first.body(), None, true)
+            .await
+            .is_ok());
+        assert!(repo
+            .update(second.id(), second.title(), first.body(), None, false)
+            .await
+            .is_ok());
+
+        drop(repo);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn legacy_array_file() {
+        let path = temporary_path();
+        std::fs::write(
+            &path,
+            r#"[{"id": 3, "title": "foo", "body": "bar", "created_at": "2024-01-01T00:00:00Z"}]"#,
+        )
+        .unwrap();
+
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+
+        assert_eq!(repo.count(false).await.unwrap(), 1);
+        assert_eq!(
+            repo.create(&Faker.fake::<CreateFactRequest>())
+                .await
+                .unwrap()
+                .id(),
+            FactId::new(4).unwrap()
+        );
+
+        drop(repo);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn concurrent_likes() {
+        let path = temporary_path();
+
+        let repo = Arc::new(
+            JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+                .await
+                .unwrap(),
+        );
+        let id = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap()
+            .id();
+
+        let likes = (0..20)
+            .map(|_| {
+                let repo = repo.clone();
+                tokio::spawn(async move { repo.like(id, false).await.unwrap() })
+            })
+            .collect::<Vec<_>>();
+        for like in likes {
+            like.await.unwrap();
+        }
+
+        assert_eq!(repo.like(id, false).await.unwrap(), 21);
+
+        drop(repo);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn changes_are_persisted_after_debounce() {
+        let path = temporary_path();
+
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_millis(10))
+            .await
+            .unwrap();
+        let first = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap();
+        let second = repo
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap();
+        repo.delete(first.id()).await.unwrap();
+        sleep(Duration::from_millis(200)).await;
+
+        let persisted: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+
+        assert_eq!(
+            persisted["facts"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|fact| fact["id"].clone())
+                .collect::<Vec<_>>(),
+            vec![i32::from(second.id())]
+        );
+        assert_eq!(persisted["next_id"], i32::from(second.id()) + 1);
+
+        drop(repo);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn locked_by_another_repository() {
+        let path = temporary_path();
+
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            JsonFileFactsRepository::open(&path, Duration::from_secs(3600)).await,
+            Err(JsonFileError::Locked { .. })
+        ));
+
+        drop(repo);
+        cleanup(&path);
+    }
+}
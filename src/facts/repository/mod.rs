@@ -1,24 +1,158 @@
 use async_trait::async_trait;
-pub use errors::{CreateFactError, DeleteFactError, GetFactError, GetRandomFactError};
+pub use batches::FactBatches;
+pub use breaker::CircuitBreakerFactsRepository;
+pub use changes::listen_changes;
+use chrono::{DateTime, NaiveDate, Utc};
+pub use errors::{
+    AddFactTagError,
+    CreateFactError,
+    DeleteFactError,
+    GetExtremesError,
+    GetFactError,
+    GetRandomFactError,
+    ListFactsError,
+    MigrationStatusError,
+    RemoveFactTagError,
+    ResyncSequenceError,
+    UpdateFactError,
+};
 pub use impls::{MockedFactsRepository, SqlxFactsRepository};
+pub use json_file::JsonFileFactsRepository;
 pub use models::{
+    ControlCharacters,
     CreateFactRequest,
     CreateFactRequestError,
     Fact,
     FactBody,
     FactId,
     FactIdError,
+    FactTag,
+    FactTagError,
     FactTitle,
+    FindFactsRequest,
+    FindFactsRequestError,
+    InvalidFact,
+    ListFactsRequest,
+    ListFactsRequestError,
+    MigrationStatus,
+    PoolStats,
+    TagMatch,
 };
+pub use pool::with_session_settings;
+pub use random::{MinMaxId, OrderByRandom, RandomStrategy, Weighted};
 
+mod batches;
+mod breaker;
+mod changes;
 mod errors;
 mod impls;
+mod json_file;
 mod models;
+mod pool;
+mod random;
 
 #[async_trait]
 pub trait FactsRepository: Send + Sync {
-    async fn get(&self, id: FactId) -> Result<Fact, GetFactError>;
+    async fn get(&self, id: FactId, public_only: bool) -> Result<Fact, GetFactError>;
     async fn get_random(&self) -> Result<Fact, GetRandomFactError>;
+    async fn get_random_excluding(&self, id: FactId) -> Result<Fact, GetRandomFactError>;
+    async fn get_random_short(&self, max_body_len: usize) -> Result<Fact, GetRandomFactError>;
+    async fn get_random_public(&self, max_body_len: usize) -> Result<Fact, GetRandomFactError>;
+    async fn get_random_recent(
+        &self,
+        max_body_len: usize,
+        public_only: bool,
+    ) -> Result<Fact, GetRandomFactError>;
+    async fn get_seeded(
+        &self,
+        seed: i64,
+        position: u32,
+        public_only: bool,
+    ) -> Result<Fact, GetRandomFactError>;
+    async fn list(&self, request: &ListFactsRequest) -> Result<Vec<Fact>, ListFactsError>;
+    async fn list_with_total(
+        &self,
+        request: &ListFactsRequest,
+    ) -> Result<(Vec<Fact>, u64), ListFactsError>;
+    async fn list_by_actor(&self, actor: &str) -> Result<Vec<Fact>, ListFactsError> {
+        let limit = ListFactsRequest::MAX_LIMIT;
+        let mut facts = Vec::new();
+
+        loop {
+            let offset = u32::try_from(facts.len()).unwrap_or(u32::MAX);
+            let page = self
+                .list(
+                    &ListFactsRequest::new(limit, offset)
+                        .expect("MAX_LIMIT is a valid limit")
+                        .with_created_by(Some(actor.to_owned())),
+                )
+                .await?;
+            let last = page.len() < limit as usize;
+            facts.extend(page);
+            if last {
+                return Ok(facts);
+            }
+        }
+    }
+    async fn list_after(
+        &self,
+        after: Option<FactId>,
+        limit: u32,
+    ) -> Result<Vec<Fact>, ListFactsError>;
+    async fn sample(&self, n: u32, seed: u64) -> Result<Vec<Fact>, ListFactsError>;
+    async fn find(&self, request: &FindFactsRequest) -> Result<Vec<Fact>, ListFactsError>;
+    async fn latest(&self, limit: u32, public_only: bool) -> Result<Vec<Fact>, ListFactsError>;
+    async fn latest_tagged(
+        &self,
+        tag: &FactTag,
+        limit: u32,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError>;
+    async fn list_by_tags(
+        &self,
+        tags: &[FactTag],
+        mode: TagMatch,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError>;
+    async fn modified_since(
+        &self,
+        since: DateTime<Utc>,
+        public_only: bool,
+    ) -> Result<Vec<Fact>, ListFactsError>;
+    async fn recent(&self, minutes: u32, public_only: bool) -> Result<Vec<Fact>, ListFactsError>;
+    async fn count(&self, public_only: bool) -> Result<u64, ListFactsError>;
+    async fn extremes(&self, public_only: bool) -> Result<(Fact, Fact), GetExtremesError>;
+    async fn neighbors(
+        &self,
+        id: FactId,
+        public_only: bool,
+    ) -> Result<(Option<FactId>, Option<FactId>), ListFactsError>;
+    async fn count_by_tag(&self, public_only: bool) -> Result<Vec<(FactTag, u64)>, ListFactsError>;
+    async fn count_by_day(
+        &self,
+        after: Option<DateTime<Utc>>,
+        before: Option<DateTime<Utc>>,
+        public_only: bool,
+    ) -> Result<Vec<(NaiveDate, u64)>, ListFactsError>;
+    async fn digest(&self, public_only: bool) -> Result<String, ListFactsError>;
     async fn create(&self, data: &CreateFactRequest) -> Result<Fact, CreateFactError>;
+    async fn create_if_absent(&self, data: &CreateFactRequest) -> Result<Fact, CreateFactError>;
+    async fn update(
+        &self,
+        id: FactId,
+        title: &FactTitle,
+        body: &FactBody,
+        updated_by: Option<&str>,
+        unique_body: bool,
+    ) -> Result<Fact, UpdateFactError>;
+    async fn like(&self, id: FactId, public_only: bool) -> Result<u64, UpdateFactError>;
     async fn delete(&self, id: FactId) -> Result<(), DeleteFactError>;
+    async fn add_tag(&self, id: FactId, tag: &FactTag) -> Result<(), AddFactTagError>;
+    async fn remove_tag(&self, id: FactId, tag: &FactTag) -> Result<(), RemoveFactTagError>;
+    async fn migration_status(&self) -> Result<MigrationStatus, MigrationStatusError>;
+    async fn resync_sequence(&self) -> Result<FactId, ResyncSequenceError>;
+    async fn validate_all(&self) -> Result<Vec<InvalidFact>, ListFactsError>;
+    fn pool_stats(&self) -> Option<PoolStats> {
+        None
+    }
 }
@@ -1,7 +1,15 @@
 use std::fmt;
 
+use chrono::{DateTime, Utc};
 #[cfg(test)]
-use fake::{faker::lorem::en::Sentence, Dummy, Fake, Faker};
+use fake::{
+    faker::lorem::en::{Sentence, Word},
+    Dummy,
+    Fake,
+    Faker,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 
 #[derive(Clone)]
@@ -10,6 +18,11 @@ pub struct Fact {
     id: FactId,
     title: FactTitle,
     body: FactBody,
+    created_at: DateTime<Utc>,
+    #[cfg_attr(test, dummy(default))]
+    created_by: Option<String>,
+    #[cfg_attr(test, dummy(default))]
+    updated_by: Option<String>,
 }
 
 #[derive(Error, Debug)]
@@ -48,14 +61,23 @@ impl From<FactBodyError> for FactError {
 }
 
 impl Fact {
-    pub fn new(id: FactId, title: &FactTitle, body: &FactBody) -> Self {
+    pub fn new(id: FactId, title: &FactTitle, body: &FactBody, created_at: DateTime<Utc>) -> Self {
         Self {
             id,
             title: title.to_owned(),
             body: body.to_owned(),
+            created_at,
+            created_by: None,
+            updated_by: None,
         }
     }
 
+    pub fn with_actors(mut self, created_by: Option<String>, updated_by: Option<String>) -> Self {
+        self.created_by = created_by;
+        self.updated_by = updated_by;
+        self
+    }
+
     pub fn id(&self) -> FactId {
         self.id
     }
@@ -67,6 +89,18 @@ impl Fact {
     pub fn body(&self) -> &FactBody {
         &self.body
     }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn created_by(&self) -> Option<&str> {
+        self.created_by.as_deref()
+    }
+
+    pub fn updated_by(&self) -> Option<&str> {
+        self.updated_by.as_deref()
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -109,7 +143,8 @@ impl FactId {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Deserialize)]
+#[serde(try_from = "String")]
 #[cfg_attr(test, derive(Eq, PartialEq, Debug))]
 pub struct FactTitle(String);
 
@@ -119,6 +154,14 @@ impl From<FactTitle> for String {
     }
 }
 
+impl TryFrom<String> for FactTitle {
+    type Error = FactTitleError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(&value)
+    }
+}
+
 #[derive(Error, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum FactTitleError {
@@ -140,7 +183,7 @@ impl Dummy<Faker> for FactTitle {
 }
 
 impl FactTitle {
-    const MAX_LENGTH: usize = 64;
+    pub const MAX_LENGTH: usize = 64;
 
     pub fn new(raw: &str) -> Result<Self, FactTitleError> {
         if raw.is_empty() {
@@ -155,7 +198,8 @@ impl FactTitle {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Deserialize)]
+#[serde(try_from = "String")]
 #[cfg_attr(test, derive(Eq, PartialEq, Debug))]
 pub struct FactBody(String);
 
@@ -165,15 +209,34 @@ impl From<FactBody> for String {
     }
 }
 
+impl TryFrom<String> for FactBody {
+    type Error = FactBodyError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(&value)
+    }
+}
+
 #[derive(Error, Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum FactBodyError {
     #[error("Body is very long: {length:?} chars")]
     TooLong { length: usize },
+    #[error("Body is too short: {length:?} chars, at least {min:?} required")]
+    TooShort { length: usize, min: usize },
+    #[error("Body contains a control character at position {position:?}")]
+    ControlCharacter { position: usize },
     #[error("Empty body is not allowed")]
     IsEmpty,
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+pub enum ControlCharacters {
+    #[default]
+    Reject,
+    Strip,
+}
+
 #[cfg(test)]
 impl Dummy<Faker> for FactBody {
     fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &Faker, _: &mut R) -> Self {
@@ -186,13 +249,37 @@ impl Dummy<Faker> for FactBody {
 }
 
 impl FactBody {
-    const MAX_LENGTH: usize = 2048;
+    pub const MAX_LENGTH: usize = 2048;
+    pub const DEFAULT_MIN_LENGTH: usize = 1;
 
     pub fn new(raw: &str) -> Result<Self, FactBodyError> {
+        Self::with_min_length(raw, Self::DEFAULT_MIN_LENGTH)
+    }
+
+    pub fn sanitize(raw: &str, mode: ControlCharacters) -> Result<String, FactBodyError> {
+        let disallowed = |c: char| c.is_control() && !matches!(c, '\n' | '\r' | '\t');
+
+        match (mode, raw.chars().position(disallowed)) {
+            (_, None) => Ok(raw.to_owned()),
+            (ControlCharacters::Reject, Some(position)) => {
+                Err(FactBodyError::ControlCharacter { position })
+            }
+            (ControlCharacters::Strip, Some(_)) => {
+                Ok(raw.chars().filter(|c| !disallowed(*c)).collect())
+            }
+        }
+    }
+
+    pub fn with_min_length(raw: &str, min: usize) -> Result<Self, FactBodyError> {
         if raw.is_empty() {
             return Err(FactBodyError::IsEmpty);
         }
 
+        let length = raw.chars().count();
+        if length < min {
+            return Err(FactBodyError::TooShort { length, min });
+        }
+
         if raw.len().gt(&Self::MAX_LENGTH) {
             return Err(FactBodyError::TooLong { length: raw.len() });
         }
@@ -201,11 +288,87 @@ impl FactBody {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FactTag(String);
+
+impl From<FactTag> for String {
+    fn from(val: FactTag) -> Self {
+        val.0
+    }
+}
+
+impl fmt::Display for FactTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Error, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum FactTagError {
+    #[error("Tag is very long: {length:?} chars")]
+    TooLong { length: usize },
+    #[error("Empty tag is not allowed")]
+    IsEmpty,
+    #[error("Tag may contain only alphanumeric characters, '-' and '_'")]
+    InvalidCharacters,
+}
+
+#[cfg(test)]
+impl Dummy<Faker> for FactTag {
+    fn dummy_with_rng<R: rand::Rng + ?Sized>(_: &Faker, _: &mut R) -> Self {
+        let raw = Word().fake::<String>();
+        match raw.char_indices().nth(Self::MAX_LENGTH) {
+            Some((idx, _)) => Self(raw[..idx].to_string()),
+            None => Self(raw),
+        }
+    }
+}
+
+impl FactTag {
+    pub const MAX_LENGTH: usize = 32;
+
+    pub fn new(raw: &str) -> Result<Self, FactTagError> {
+        if raw.is_empty() {
+            return Err(FactTagError::IsEmpty);
+        }
+
+        if raw.len().gt(&Self::MAX_LENGTH) {
+            return Err(FactTagError::TooLong { length: raw.len() });
+        }
+
+        if !raw
+            .chars()
+            .all(|c| c.is_alphanumeric() || c.eq(&'-') || c.eq(&'_'))
+        {
+            return Err(FactTagError::InvalidCharacters);
+        }
+
+        Ok(Self(raw.to_string()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TagMatch {
+    #[default]
+    Any,
+    All,
+}
+
 #[derive(Clone)]
 #[cfg_attr(test, derive(Dummy, Eq, PartialEq, Debug))]
 pub struct CreateFactRequest {
     title: FactTitle,
     body: FactBody,
+    created_by: Option<String>,
+    #[cfg_attr(test, dummy(expr = "true"))]
+    public: bool,
+    #[cfg_attr(test, dummy(expr = "false"))]
+    unique_body: bool,
+    #[cfg_attr(test, dummy(expr = "None"))]
+    max_total: Option<u64>,
+    #[cfg_attr(test, dummy(expr = "Vec::new()"))]
+    tags: Vec<FactTag>,
 }
 
 #[derive(Error, Debug)]
@@ -238,9 +401,39 @@ impl CreateFactRequest {
         Self {
             title: title.to_owned(),
             body: body.to_owned(),
+            created_by: None,
+            public: true,
+            unique_body: false,
+            max_total: None,
+            tags: Vec::new(),
         }
     }
 
+    pub fn with_created_by(mut self, created_by: Option<String>) -> Self {
+        self.created_by = created_by;
+        self
+    }
+
+    pub fn with_public(mut self, public: bool) -> Self {
+        self.public = public;
+        self
+    }
+
+    pub fn with_unique_body(mut self, unique_body: bool) -> Self {
+        self.unique_body = unique_body;
+        self
+    }
+
+    pub fn with_max_total(mut self, max_total: Option<u64>) -> Self {
+        self.max_total = max_total;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<FactTag>) -> Self {
+        self.tags = tags;
+        self
+    }
+
     pub fn title(&self) -> &FactTitle {
         &self.title
     }
@@ -248,6 +441,312 @@ impl CreateFactRequest {
     pub fn body(&self) -> &FactBody {
         &self.body
     }
+
+    pub fn created_by(&self) -> Option<&str> {
+        self.created_by.as_deref()
+    }
+
+    pub fn public(&self) -> bool {
+        self.public
+    }
+
+    pub fn unique_body(&self) -> bool {
+        self.unique_body
+    }
+
+    pub fn max_total(&self) -> Option<u64> {
+        self.max_total
+    }
+
+    pub fn tags(&self) -> &[FactTag] {
+        &self.tags
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(test, derive(Eq, PartialEq))]
+pub struct ListFactsRequest {
+    limit: u32,
+    offset: u32,
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
+    created_by: Option<String>,
+    tags: Vec<FactTag>,
+    tag_match: TagMatch,
+    public_only: bool,
+}
+
+#[derive(Error, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum ListFactsRequestError {
+    #[error("Limit should be between 1 and {max}, got {limit}")]
+    LimitOutOfRange { limit: u32, max: u32 },
+}
+
+impl Default for ListFactsRequest {
+    fn default() -> Self {
+        Self {
+            limit: Self::DEFAULT_LIMIT,
+            offset: 0,
+            created_after: None,
+            created_before: None,
+            created_by: None,
+            tags: Vec::new(),
+            tag_match: TagMatch::Any,
+            public_only: false,
+        }
+    }
+}
+
+impl ListFactsRequest {
+    pub const DEFAULT_LIMIT: u32 = 10;
+    pub const MAX_LIMIT: u32 = 50;
+
+    pub fn new(limit: u32, offset: u32) -> Result<Self, ListFactsRequestError> {
+        if limit.eq(&0) || limit.gt(&Self::MAX_LIMIT) {
+            return Err(ListFactsRequestError::LimitOutOfRange {
+                limit,
+                max: Self::MAX_LIMIT,
+            });
+        }
+
+        Ok(Self {
+            limit,
+            offset,
+            ..Default::default()
+        })
+    }
+
+    pub fn with_created_after(mut self, created_after: Option<DateTime<Utc>>) -> Self {
+        self.created_after = created_after;
+        self
+    }
+
+    pub fn with_created_before(mut self, created_before: Option<DateTime<Utc>>) -> Self {
+        self.created_before = created_before;
+        self
+    }
+
+    pub fn with_created_by(mut self, created_by: Option<String>) -> Self {
+        self.created_by = created_by;
+        self
+    }
+
+    /// Keeps only facts tagged with any (or all) of `tags`; empty `tags`
+    /// disables the filter.
+    pub fn with_tags(mut self, tags: Vec<FactTag>, tag_match: TagMatch) -> Self {
+        self.tags = tags;
+        self.tag_match = tag_match;
+        self
+    }
+
+    pub fn with_public_only(mut self, public_only: bool) -> Self {
+        self.public_only = public_only;
+        self
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub fn created_after(&self) -> Option<DateTime<Utc>> {
+        self.created_after
+    }
+
+    pub fn created_before(&self) -> Option<DateTime<Utc>> {
+        self.created_before
+    }
+
+    pub fn created_by(&self) -> Option<&str> {
+        self.created_by.as_deref()
+    }
+
+    pub fn tags(&self) -> &[FactTag] {
+        &self.tags
+    }
+
+    pub fn tag_match(&self) -> TagMatch {
+        self.tag_match
+    }
+
+    pub fn public_only(&self) -> bool {
+        self.public_only
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(test, derive(Eq, PartialEq))]
+pub struct FindFactsRequest {
+    title_contains: Option<String>,
+    body_contains: Option<String>,
+    tag: Option<FactTag>,
+    limit: u32,
+    public_only: bool,
+}
+
+#[derive(Error, Debug)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum FindFactsRequestError {
+    #[error("At least one matcher should be provided")]
+    NoMatchers,
+    #[error("Matcher value for '{field}' shouldn't be empty")]
+    EmptyMatcher { field: &'static str },
+}
+
+impl FindFactsRequest {
+    pub const DEFAULT_LIMIT: u32 = 50;
+
+    pub fn new(
+        title_contains: Option<String>,
+        body_contains: Option<String>,
+        tag: Option<FactTag>,
+    ) -> Result<Self, FindFactsRequestError> {
+        if title_contains.is_none() && body_contains.is_none() && tag.is_none() {
+            return Err(FindFactsRequestError::NoMatchers);
+        }
+        if title_contains.as_deref().is_some_and(str::is_empty) {
+            return Err(FindFactsRequestError::EmptyMatcher {
+                field: "title_contains",
+            });
+        }
+        if body_contains.as_deref().is_some_and(str::is_empty) {
+            return Err(FindFactsRequestError::EmptyMatcher {
+                field: "body_contains",
+            });
+        }
+
+        Ok(Self {
+            title_contains,
+            body_contains,
+            tag,
+            limit: Self::DEFAULT_LIMIT,
+            public_only: false,
+        })
+    }
+
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn with_public_only(mut self, public_only: bool) -> Self {
+        self.public_only = public_only;
+        self
+    }
+
+    pub fn title_contains(&self) -> Option<&str> {
+        self.title_contains.as_deref()
+    }
+
+    pub fn body_contains(&self) -> Option<&str> {
+        self.body_contains.as_deref()
+    }
+
+    pub fn tag(&self) -> Option<&FactTag> {
+        self.tag.as_ref()
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    pub fn public_only(&self) -> bool {
+        self.public_only
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(test, derive(Eq, PartialEq))]
+pub struct MigrationStatus {
+    applied: Vec<i64>,
+    pending: Vec<i64>,
+}
+
+impl MigrationStatus {
+    pub fn new(applied: Vec<i64>, pending: Vec<i64>) -> Self {
+        Self { applied, pending }
+    }
+
+    pub fn applied(&self) -> &[i64] {
+        &self.applied
+    }
+
+    pub fn pending(&self) -> &[i64] {
+        &self.pending
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(test, derive(Eq, PartialEq))]
+pub struct InvalidFact {
+    id: i32,
+    reasons: Vec<String>,
+}
+
+impl InvalidFact {
+    pub fn check(id: i32, title: &str, body: &str) -> Option<Self> {
+        let reasons = [
+            FactId::new(id).err().map(FactError::from),
+            FactTitle::new(title).err().map(FactError::from),
+            FactBody::new(body).err().map(FactError::from),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|err| err.to_string())
+        .collect::<Vec<_>>();
+
+        (!reasons.is_empty()).then_some(Self { id, reasons })
+    }
+
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn reasons(&self) -> &[String] {
+        &self.reasons
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PoolStats {
+    size: u32,
+    idle: u32,
+}
+
+impl PoolStats {
+    pub fn new(size: u32, idle: u32) -> Self {
+        Self { size, idle }
+    }
+
+    pub fn size(self) -> u32 {
+        self.size
+    }
+
+    pub fn idle(self) -> u32 {
+        self.idle
+    }
+
+    pub fn in_use(self) -> u32 {
+        self.size.saturating_sub(self.idle)
+    }
+}
+
+#[derive(Default)]
+pub struct FactsDigest(Sha256);
+
+impl FactsDigest {
+    pub fn update(&mut self, id: i32, updated_at: DateTime<Utc>) {
+        self.0.update(id.to_be_bytes());
+        self.0.update(updated_at.timestamp_micros().to_be_bytes());
+    }
+
+    pub fn finish(self) -> String {
+        format!("{:x}", self.0.finalize())
+    }
 }
 
 #[cfg(test)]
@@ -269,11 +768,115 @@ mod tests {
         assert_eq!(FactTitle::new(""), Err(FactTitleError::IsEmpty));
     }
 
+    #[test]
+    fn deserialize_invalid_title() {
+        let err = serde_json::from_str::<FactTitle>("\"\"")
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("Empty title is not allowed"), "{err}");
+
+        let raw = "a".repeat(FactTitle::MAX_LENGTH + 1);
+        let err = serde_json::from_value::<FactTitle>(raw.into())
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("Title is very long"), "{err}");
+    }
+
+    #[test]
+    fn deserialize_invalid_body() {
+        let err = serde_json::from_str::<FactBody>("\"\"")
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("Empty body is not allowed"), "{err}");
+
+        let raw = "a".repeat(FactBody::MAX_LENGTH + 1);
+        let err = serde_json::from_value::<FactBody>(raw.into())
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("Body is very long"), "{err}");
+    }
+
+    #[test]
+    fn deserialize_valid_title() {
+        assert_eq!(
+            serde_json::from_str::<FactTitle>("\"Octopuses\"").unwrap(),
+            FactTitle::new("Octopuses").unwrap()
+        );
+    }
+
     #[test]
     fn empty_body() {
         assert_eq!(FactBody::new(""), Err(FactBodyError::IsEmpty));
     }
 
+    #[test]
+    fn body_with_control_characters() {
+        let body = "Octopuses\0 have\tthree\nhearts";
+
+        assert_eq!(
+            FactBody::sanitize(body, ControlCharacters::Reject),
+            Err(FactBodyError::ControlCharacter { position: 9 })
+        );
+        assert_eq!(
+            FactBody::sanitize(body, ControlCharacters::Strip).unwrap(),
+            "Octopuses have\tthree\nhearts"
+        );
+    }
+
+    #[test]
+    fn short_body() {
+        assert!(FactBody::with_min_length("Octopuses", 9).is_ok());
+        assert_eq!(
+            FactBody::with_min_length("Octopus", 9),
+            Err(FactBodyError::TooShort { length: 7, min: 9 })
+        );
+    }
+
+    #[test]
+    fn list_limit_out_of_range() {
+        assert_eq!(
+            ListFactsRequest::new(0, 0),
+            Err(ListFactsRequestError::LimitOutOfRange {
+                limit: 0,
+                max: ListFactsRequest::MAX_LIMIT
+            })
+        );
+        assert_eq!(
+            ListFactsRequest::new(ListFactsRequest::MAX_LIMIT + 1, 0),
+            Err(ListFactsRequestError::LimitOutOfRange {
+                limit: ListFactsRequest::MAX_LIMIT + 1,
+                max: ListFactsRequest::MAX_LIMIT
+            })
+        );
+    }
+
+    #[test]
+    fn empty_tag() {
+        assert_eq!(FactTag::new(""), Err(FactTagError::IsEmpty));
+    }
+
+    #[test]
+    fn long_tag() {
+        let tag = "a".repeat(FactTag::MAX_LENGTH + 1);
+
+        assert_eq!(
+            FactTag::new(&tag),
+            Err(FactTagError::TooLong { length: tag.len() })
+        );
+    }
+
+    #[test]
+    fn tag_with_whitespace() {
+        assert_eq!(
+            FactTag::new("two words"),
+            Err(FactTagError::InvalidCharacters)
+        );
+    }
+
     #[test]
     fn long_title() {
         let title = ((FactTitle::MAX_LENGTH + 1)..(FactTitle::MAX_LENGTH * 2)).fake::<String>();
@@ -295,4 +898,50 @@ mod tests {
             Err(FactBodyError::TooLong { length: body.len() })
         );
     }
+
+    fn openapi_max_length(schema: &str) -> Option<usize> {
+        include_str!("../../../openapi.yaml")
+            .split(&format!("\n    {schema}:\n"))
+            .nth(1)?
+            .lines()
+            .take_while(|line| line.starts_with("      "))
+            .find_map(|line| line.trim().strip_prefix("maxLength: "))?
+            .parse()
+            .ok()
+    }
+
+    #[test]
+    fn openapi_max_lengths() {
+        assert_eq!(openapi_max_length("FactTitle"), Some(FactTitle::MAX_LENGTH));
+        assert_eq!(openapi_max_length("FactBody"), Some(FactBody::MAX_LENGTH));
+        assert_eq!(openapi_max_length("FactTag"), Some(FactTag::MAX_LENGTH));
+    }
+
+    #[test]
+    fn find_without_matchers() {
+        assert_eq!(
+            FindFactsRequest::new(None, None, None),
+            Err(FindFactsRequestError::NoMatchers)
+        );
+    }
+
+    #[test]
+    fn find_with_empty_matcher() {
+        assert_eq!(
+            FindFactsRequest::new(Some(String::new()), None, None),
+            Err(FindFactsRequestError::EmptyMatcher {
+                field: "title_contains"
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_fact_reasons() {
+        assert_eq!(InvalidFact::check(1, "Title", "Body"), None);
+
+        let invalid = InvalidFact::check(0, &"a".repeat(65), "Body").unwrap();
+
+        assert_eq!(invalid.id(), 0);
+        assert_eq!(invalid.reasons().len(), 2);
+    }
 }
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use sqlx::{postgres::PgPoolOptions, Executor};
+
+use super::changes::NOTIFY_CHANGES_SETTING;
+
+pub fn with_session_settings(
+    options: PgPoolOptions,
+    statement_timeout: Option<Duration>,
+    suppress_change_notifications: bool,
+) -> PgPoolOptions {
+    let mut statements = Vec::new();
+    if let Some(timeout) = statement_timeout {
+        statements.push(format!("SET statement_timeout = {}", timeout.as_millis()));
+    }
+    // The facts_changed trigger only skips pg_notify when this is "off", so
+    // writes from other clients keep notifying. Listeners elsewhere never see
+    // writes made through this pool, which is why it is opt-in.
+    if suppress_change_notifications {
+        statements.push(format!("SET {NOTIFY_CHANGES_SETTING} = off"));
+    }
+
+    if statements.is_empty() {
+        return options;
+    }
+    let statement = statements.join("; ");
+
+    options.after_connect(move |connection, _| {
+        let statement = statement.clone();
+        Box::pin(async move {
+            connection.execute(statement.as_str()).await?;
+            Ok(())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::{Fake, Faker};
+    use sqlx::{postgres::PgConnectOptions, query, PgPool};
+    use tokio::time::timeout;
+
+    use super::*;
+    use crate::facts::repository::{
+        listen_changes,
+        CreateFactRequest,
+        FactsRepository,
+        SqlxFactsRepository,
+    };
+
+    const QUERY_CANCELED: &str = "57014";
+
+    #[sqlx::test]
+    async fn slow_query_is_aborted(pool_options: PgPoolOptions, connect_options: PgConnectOptions) {
+        let pool = with_session_settings(pool_options, Some(Duration::from_millis(100)), false)
+            .connect_with(connect_options)
+            .await
+            .unwrap();
+
+        let err = query!("SELECT pg_sleep(5)")
+            .execute(&pool)
+            .await
+            .unwrap_err();
+
+        let code = err.as_database_error().unwrap().code();
+
+        assert_eq!(code.as_deref(), Some(QUERY_CANCELED));
+
+        query!("SELECT pg_sleep(0.01)")
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn change_notifications_suppressed(pool: PgPool) {
+        let mut changes = listen_changes(&pool).await.unwrap().subscribe();
+        let quiet = with_session_settings(PgPoolOptions::new(), None, true)
+            .connect_with((*pool.connect_options()).clone())
+            .await
+            .unwrap();
+
+        SqlxFactsRepository::new(quiet)
+            .create(&Faker.fake::<CreateFactRequest>())
+            .await
+            .unwrap();
+
+        assert!(timeout(Duration::from_millis(500), changes.recv())
+            .await
+            .is_err());
+    }
+}
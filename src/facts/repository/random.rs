@@ -0,0 +1,218 @@
+use async_trait::async_trait;
+use sqlx::{query_as, PgPool};
+
+use super::impls::SqlxFact;
+
+#[async_trait]
+pub trait RandomStrategy: Send + Sync {
+    async fn choose(
+        &self,
+        pool: &PgPool,
+        max_body_len: i32,
+        public_only: bool,
+    ) -> Result<Option<SqlxFact>, sqlx::Error>;
+}
+
+pub struct OrderByRandom;
+
+#[async_trait]
+impl RandomStrategy for OrderByRandom {
+    async fn choose(
+        &self,
+        pool: &PgPool,
+        max_body_len: i32,
+        public_only: bool,
+    ) -> Result<Option<SqlxFact>, sqlx::Error> {
+        query_as!(
+            SqlxFact,
+            r"
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE length(body) <= $1 AND (public OR NOT $2)
+ORDER BY random()
+LIMIT 1
+        ",
+            max_body_len,
+            public_only,
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
+
+pub struct MinMaxId;
+
+#[async_trait]
+impl RandomStrategy for MinMaxId {
+    async fn choose(
+        &self,
+        pool: &PgPool,
+        max_body_len: i32,
+        public_only: bool,
+    ) -> Result<Option<SqlxFact>, sqlx::Error> {
+        query_as!(
+            SqlxFact,
+            r"
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE length(body) <= $1 AND (public OR NOT $2) AND id >= (
+  SELECT floor(random() * (max(id) - min(id) + 1))::integer + min(id)
+  FROM facts
+  WHERE length(body) <= $1 AND (public OR NOT $2)
+)
+ORDER BY id
+LIMIT 1
+        ",
+            max_body_len,
+            public_only,
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
+
+pub struct Weighted;
+
+#[async_trait]
+impl RandomStrategy for Weighted {
+    async fn choose(
+        &self,
+        pool: &PgPool,
+        max_body_len: i32,
+        public_only: bool,
+    ) -> Result<Option<SqlxFact>, sqlx::Error> {
+        query_as!(
+            SqlxFact,
+            r"
+SELECT
+  id, title, body, created_at, created_by, updated_by
+FROM facts
+WHERE length(body) <= $1 AND (public OR NOT $2)
+ORDER BY -ln(1.0 - random()) / length(body)
+LIMIT 1
+        ",
+            max_body_len,
+            public_only,
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::{Fake, Faker};
+    use sqlx::{query, query_scalar};
+
+    use super::*;
+    use crate::facts::repository::Fact;
+
+    async fn seed(pool: &PgPool) -> Vec<i32> {
+        let mut ids = Vec::new();
+
+        for _ in 0..8 {
+            let entity: SqlxFact = Faker.fake::<Fact>().into();
+
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                entity.title,
+                entity.body,
+            )
+            .fetch_one(pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
+        ids
+    }
+
+    async fn assert_chooses_seeded(strategy: &dyn RandomStrategy, pool: &PgPool) {
+        let ids = seed(pool).await;
+
+        for _ in 0..16 {
+            let chosen = strategy
+                .choose(pool, i32::MAX, false)
+                .await
+                .unwrap()
+                .unwrap();
+
+            assert!(ids.contains(&chosen.id));
+            assert!(Fact::try_from(chosen).is_ok());
+        }
+    }
+
+    async fn assert_chooses_public(strategy: &dyn RandomStrategy, pool: &PgPool) {
+        let ids = seed(pool).await;
+        query!("UPDATE facts SET public = (id = $1)", ids[3])
+            .execute(pool)
+            .await
+            .unwrap();
+
+        for _ in 0..16 {
+            let chosen = strategy
+                .choose(pool, i32::MAX, true)
+                .await
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(chosen.id, ids[3]);
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn public_only(pool: PgPool) {
+        for strategy in [&OrderByRandom as &dyn RandomStrategy, &MinMaxId, &Weighted] {
+            query!("TRUNCATE facts CASCADE")
+                .execute(&pool)
+                .await
+                .unwrap();
+            assert_chooses_public(strategy, &pool).await;
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn order_by_random(pool: PgPool) {
+        assert!(OrderByRandom
+            .choose(&pool, i32::MAX, false)
+            .await
+            .unwrap()
+            .is_none());
+        assert_chooses_seeded(&OrderByRandom, &pool).await;
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn min_max_id(pool: PgPool) {
+        assert!(MinMaxId
+            .choose(&pool, i32::MAX, false)
+            .await
+            .unwrap()
+            .is_none());
+        assert_chooses_seeded(&MinMaxId, &pool).await;
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn weighted(pool: PgPool) {
+        assert!(Weighted
+            .choose(&pool, i32::MAX, false)
+            .await
+            .unwrap()
+            .is_none());
+        assert_chooses_seeded(&Weighted, &pool).await;
+    }
+}
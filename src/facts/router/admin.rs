@@ -0,0 +1,412 @@
+use std::sync::atomic::Ordering;
+
+use axum::{
+    body::Body,
+    debug_handler,
+    extract::State,
+    http::{header::CONTENT_TYPE, StatusCode},
+    middleware::from_fn_with_state,
+    response::IntoResponse,
+    routing::{get, post},
+    Json,
+    Router,
+};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{error, warn};
+
+use super::{
+    errors::AppError,
+    handlers::auth_middleware,
+    models::{
+        HttpFactResponse,
+        HttpMigrationStatusResponse,
+        HttpResyncSequenceResponse,
+        HttpValidationReportResponse,
+    },
+    state::AppState,
+};
+use crate::facts::repository::FactBatches;
+
+const EXPORT_BATCH_SIZE: u32 = 500;
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+pub struct AdminRouter {
+    state: AppState,
+}
+
+impl AdminRouter {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+}
+
+#[debug_handler]
+pub async fn migrations(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let status = state.facts.migration_status().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(HttpMigrationStatusResponse::from(status)),
+    ))
+}
+
+#[debug_handler]
+pub async fn validate_all(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let invalid = state.facts.validate_all().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(HttpValidationReportResponse::from(invalid)),
+    ))
+}
+
+#[debug_handler]
+pub async fn resync_sequence(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let next_id = state.facts.resync_sequence().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(HttpResyncSequenceResponse::from(next_id)),
+    ))
+}
+
+#[debug_handler]
+pub async fn export(State(state): State<AppState>) -> impl IntoResponse {
+    let (sender, receiver) = mpsc::channel(1);
+    let expose_actors = state.expose_actors;
+    let mut batches = FactBatches::new(state.facts, EXPORT_BATCH_SIZE);
+
+    tokio::spawn(async move {
+        while let Some(batch) = batches.next_batch().await {
+            let chunk = batch
+                .inspect_err(|err| error!("Export aborted: {err}"))
+                .map(|facts| {
+                    facts.into_iter().fold(String::new(), |mut chunk, fact| {
+                        if let Ok(line) =
+                            serde_json::to_string(&HttpFactResponse::new(&fact, expose_actors))
+                        {
+                            chunk.push_str(&line);
+                            chunk.push('\n');
+                        }
+                        chunk
+                    })
+                });
+
+            if sender.send(chunk).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    (
+        StatusCode::OK,
+        [(CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+        Body::from_stream(ReceiverStream::new(receiver)),
+    )
+}
+
+#[debug_handler]
+pub async fn drain(State(state): State<AppState>) -> impl IntoResponse {
+    if !state.draining.swap(true, Ordering::Relaxed) {
+        warn!("Draining requested, readiness probe will fail from now on");
+    }
+
+    StatusCode::NO_CONTENT
+}
+
+impl From<AdminRouter> for Router<AppState> {
+    fn from(admin_router: AdminRouter) -> Self {
+        Router::new()
+            .route("/drain", post(drain))
+            .route("/migrations", get(migrations))
+            .route("/validate-all", get(validate_all))
+            .route("/export", get(export))
+            .route("/resync-sequence", post(resync_sequence))
+            .route_layer(from_fn_with_state(admin_router.state, auth_middleware))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use reqwest::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        Method,
+    };
+    use serde_json::from_slice;
+    use sqlx::{query_scalar, PgPool};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::facts::{AppRouter, SqlxFactsRepository};
+
+    fn router(state: AppState) -> Router {
+        Router::new()
+            .nest("/api/facts", AppRouter::new(state.clone()).into())
+            .nest("/api/admin", AdminRouter::new(state.clone()).into())
+            .with_state(state)
+    }
+
+    async fn status(router: Router, method: Method, uri: &str) -> StatusCode {
+        router
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn drain() {
+        let router = router(AppState::default());
+
+        assert_eq!(
+            status(router.clone(), Method::GET, "/api/facts/readyz").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(router.clone(), Method::POST, "/api/admin/drain").await,
+            StatusCode::NO_CONTENT
+        );
+        assert_eq!(
+            status(router.clone(), Method::GET, "/api/facts/readyz").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status(router.clone(), Method::GET, "/api/facts/health").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(router, Method::GET, "/api/facts/1").await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn drain_unauthorized() {
+        let state = AppState::default();
+
+        let raw_response = router(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/admin/drain")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::UNAUTHORIZED);
+        assert!(!state.draining.load(Ordering::Relaxed));
+    }
+
+    #[sqlx::test(migrations = "./src/facts/migrations")]
+    async fn pending_migration(pool: PgPool) {
+        let version: i64 = query_scalar(
+            "DELETE FROM _sqlx_migrations WHERE version = (SELECT max(version) FROM _sqlx_migrations) RETURNING version",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let raw_response = router(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/admin/migrations")
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        let response = from_slice::<HttpMigrationStatusResponse>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(response.pending(), [version]);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn validate_all(pool: PgPool) {
+        query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            "Short title",
+            "Short body",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let invalid = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            "ё".repeat(64),
+            "Short body",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let raw_response = router(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/admin/validate-all")
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        let response = from_slice::<HttpValidationReportResponse>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(response.invalid_ids(), [invalid]);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn export(pool: PgPool) {
+        let mut ids = Vec::new();
+        for idx in 0..3 {
+            ids.push(
+                query_scalar!(
+                    "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                    format!("Fact {idx}"),
+                    "body",
+                )
+                .fetch_one(&pool)
+                .await
+                .unwrap(),
+            );
+        }
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let raw_response = router(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/api/admin/export")
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+        assert_eq!(raw_response.headers()[CONTENT_TYPE], NDJSON_CONTENT_TYPE);
+
+        let raw_export = String::from_utf8(
+            raw_response
+                .into_body()
+                .collect()
+                .await
+                .unwrap()
+                .to_bytes()
+                .to_vec(),
+        )
+        .unwrap();
+        let exported = raw_export
+            .lines()
+            .map(|line| {
+                from_slice::<HttpFactResponse>(line.as_bytes())
+                    .unwrap()
+                    .id()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(exported, ids);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn resync_sequence(pool: PgPool) {
+        query_scalar!(
+            "INSERT INTO facts (id, title, body) VALUES ($1, $2, $3) RETURNING id",
+            1,
+            "Imported title",
+            "Imported body",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let raw_response = router(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/admin/resync-sequence")
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        let response = from_slice::<HttpResyncSequenceResponse>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(response.next_id(), 2);
+
+        let raw_response = router(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/facts")
+                    .header(CONTENT_TYPE, "application/json")
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::from(r#"{"title": "foo", "body": "bar"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::CREATED);
+    }
+}
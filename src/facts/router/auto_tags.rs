@@ -0,0 +1,161 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+use tokio::fs;
+
+use crate::facts::repository::FactTag;
+
+#[derive(Error, Debug)]
+pub enum AutoTagRulesError {
+    #[error("Cannot access {path:?}: {inner}")]
+    Io { path: PathBuf, inner: String },
+    #[error("Cannot parse {path:?}: {inner}")]
+    InvalidContent { path: PathBuf, inner: String },
+    #[error("Rule for keyword '{keyword}' has an invalid tag: {inner}")]
+    InvalidTag { keyword: String, inner: String },
+}
+
+#[derive(Debug, Default)]
+pub struct AutoTagRules {
+    rules: Vec<(String, FactTag)>,
+}
+
+impl AutoTagRules {
+    pub async fn load(path: &Path) -> Result<Self, AutoTagRulesError> {
+        let content = fs::read(path).await.map_err(|err| AutoTagRulesError::Io {
+            path: path.to_owned(),
+            inner: err.to_string(),
+        })?;
+        let rules: BTreeMap<String, String> =
+            serde_json::from_slice(&content).map_err(|err| AutoTagRulesError::InvalidContent {
+                path: path.to_owned(),
+                inner: err.to_string(),
+            })?;
+
+        Self::new(rules)
+    }
+
+    pub fn new(rules: BTreeMap<String, String>) -> Result<Self, AutoTagRulesError> {
+        let rules = rules
+            .into_iter()
+            .filter(|(keyword, _)| !keyword.is_empty())
+            .map(|(keyword, tag)| match FactTag::new(&tag) {
+                Ok(tag) => Ok((keyword.to_lowercase(), tag)),
+                Err(err) => Err(AutoTagRulesError::InvalidTag {
+                    keyword,
+                    inner: err.to_string(),
+                }),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { rules })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn tags_for(&self, body: &str) -> Vec<FactTag> {
+        let body = body.to_lowercase();
+        let mut tags: Vec<FactTag> = Vec::new();
+
+        for (keyword, tag) in &self.rules {
+            if contains_word(&body, keyword) && !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+
+        tags
+    }
+}
+
+fn contains_word(haystack: &str, word: &str) -> bool {
+    haystack.match_indices(word).any(|(start, _)| {
+        let end = start + word.len();
+
+        !haystack[..start]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric)
+            && !haystack[end..]
+                .chars()
+                .next()
+                .is_some_and(char::is_alphanumeric)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use super::*;
+
+    fn rules(raw: &[(&str, &str)]) -> Result<AutoTagRules, AutoTagRulesError> {
+        AutoTagRules::new(
+            raw.iter()
+                .map(|(keyword, tag)| ((*keyword).to_owned(), (*tag).to_owned()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn matching_keywords() {
+        let rules = rules(&[
+            ("smoking", "health"),
+            ("tobacco", "health"),
+            ("Moon", "space"),
+            ("volcano", "geology"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            rules.tags_for("Smoking and tobacco are bad, the moon is far"),
+            vec![
+                FactTag::new("space").unwrap(),
+                FactTag::new("health").unwrap()
+            ]
+        );
+        assert_eq!(rules.tags_for("Nothing to see here"), vec![]);
+    }
+
+    #[test]
+    fn matching_whole_words_only() {
+        let rules = rules(&[("art", "culture"), ("deep sea", "ocean")]).unwrap();
+
+        assert_eq!(rules.tags_for("The party started late"), vec![]);
+        assert_eq!(rules.tags_for("Artists love the deep seas"), vec![]);
+        assert_eq!(
+            rules.tags_for("Cave art is old; so is the deep sea."),
+            vec![
+                FactTag::new("culture").unwrap(),
+                FactTag::new("ocean").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_tag() {
+        assert!(matches!(
+            rules(&[("smoking", "bad health")]),
+            Err(AutoTagRulesError::InvalidTag { keyword, .. }) if keyword == "smoking"
+        ));
+    }
+
+    #[tokio::test]
+    async fn load_from_file() {
+        let path = temp_dir().join(format!("auto-tags-{}.json", std::process::id()));
+        fs::write(&path, r#"{"octopus": "biology"}"#).await.unwrap();
+
+        let rules = AutoTagRules::load(&path).await.unwrap();
+        fs::remove_file(&path).await.unwrap();
+
+        assert_eq!(rules.len(), 1);
+        assert_eq!(
+            rules.tags_for("An octopus has three hearts"),
+            vec![FactTag::new("biology").unwrap()]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt::Write;
+
+use super::feed::escape;
+use crate::facts::repository::Fact;
+
+pub const SVG_CONTENT_TYPE: &str = "image/svg+xml";
+
+const CARD_WIDTH: u32 = 1200;
+const CARD_HEIGHT: u32 = 630;
+const CARD_PADDING: u32 = 80;
+const TITLE_FONT_SIZE: u32 = 56;
+const TITLE_LINE_HEIGHT: u32 = 70;
+const TITLE_LINE_CHARS: usize = 32;
+const TITLE_MAX_LINES: usize = 2;
+const BODY_FONT_SIZE: u32 = 34;
+const BODY_LINE_HEIGHT: u32 = 48;
+const BODY_LINE_CHARS: usize = 56;
+const BODY_MAX_LINES: usize = 6;
+const FOOTER_FONT_SIZE: u32 = 24;
+
+fn wrap(text: &str, width: usize, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+
+    for word in text.split_whitespace() {
+        let chars = word.chars().collect::<Vec<_>>();
+        for chunk in chars.chunks(width) {
+            if current_len > 0 && current_len + 1 + chunk.len() > width {
+                lines.push(std::mem::take(&mut current));
+                current_len = 0;
+            }
+            if current_len > 0 {
+                current.push(' ');
+                current_len += 1;
+            }
+            current.extend(chunk);
+            current_len += chunk.len();
+        }
+    }
+    if current_len > 0 {
+        lines.push(current);
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        if let Some(last) = lines.last_mut() {
+            let kept = last.chars().take(width - 1).collect::<String>();
+            *last = format!("{}…", kept.trim_end());
+        }
+    }
+
+    lines
+}
+
+pub fn render_card(fact: &Fact) -> String {
+    let title = wrap(
+        &String::from(fact.title().to_owned()),
+        TITLE_LINE_CHARS,
+        TITLE_MAX_LINES,
+    );
+    let body = wrap(
+        &String::from(fact.body().to_owned()),
+        BODY_LINE_CHARS,
+        BODY_MAX_LINES,
+    );
+    let mut card = String::new();
+
+    let _ = write!(
+        card,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{CARD_WIDTH}" height="{CARD_HEIGHT}" viewBox="0 0 {CARD_WIDTH} {CARD_HEIGHT}">"#
+    );
+    let _ = write!(
+        card,
+        r##"<rect width="{CARD_WIDTH}" height="{CARD_HEIGHT}" fill="#1e293b"/>"##
+    );
+
+    let mut y = CARD_PADDING + TITLE_FONT_SIZE;
+    for line in &title {
+        let _ = write!(
+            card,
+            r##"<text x="{CARD_PADDING}" y="{y}" font-family="sans-serif" font-size="{TITLE_FONT_SIZE}" font-weight="bold" fill="#f8fafc">{}</text>"##,
+            escape(line)
+        );
+        y += TITLE_LINE_HEIGHT;
+    }
+
+    y += BODY_LINE_HEIGHT - BODY_FONT_SIZE;
+    for line in &body {
+        let _ = write!(
+            card,
+            r##"<text x="{CARD_PADDING}" y="{y}" font-family="sans-serif" font-size="{BODY_FONT_SIZE}" fill="#cbd5e1">{}</text>"##,
+            escape(line)
+        );
+        y += BODY_LINE_HEIGHT;
+    }
+
+    let _ = write!(
+        card,
+        r##"<text x="{CARD_PADDING}" y="{}" font-family="sans-serif" font-size="{FOOTER_FONT_SIZE}" fill="#64748b">Fact #{}</text>"##,
+        CARD_HEIGHT - CARD_PADDING / 2,
+        fact.id()
+    );
+    card.push_str("</svg>");
+    card
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapping() {
+        assert_eq!(
+            wrap("The quick brown fox jumps over the lazy dog", 10, 5),
+            ["The quick", "brown fox", "jumps over", "the lazy", "dog"]
+        );
+        assert_eq!(
+            wrap("Pneumonoultramicroscopic  words", 10, 5),
+            ["Pneumonoul", "tramicrosc", "opic words"]
+        );
+        assert_eq!(
+            wrap("The quick brown fox jumps over the lazy dog", 10, 2),
+            ["The quick", "brown fox…"]
+        );
+        assert!(wrap("  ", 10, 2).is_empty());
+    }
+}
@@ -1,17 +1,29 @@
 use axum::{
-    http::StatusCode,
+    http::{header::RETRY_AFTER, StatusCode},
     response::{IntoResponse, Response},
 };
 
 use crate::facts::repository::{
+    AddFactTagError,
     CreateFactError,
     CreateFactRequestError,
     DeleteFactError,
     FactIdError,
+    FactTagError,
+    FindFactsRequestError,
+    GetExtremesError,
     GetFactError,
     GetRandomFactError,
+    ListFactsError,
+    ListFactsRequestError,
+    MigrationStatusError,
+    RemoveFactTagError,
+    ResyncSequenceError,
+    UpdateFactError,
 };
 
+const RETRY_AFTER_SECONDS: u64 = 1;
+
 pub struct AppError {
     pub status_code: StatusCode,
     pub details: String,
@@ -19,7 +31,16 @@ pub struct AppError {
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        (self.status_code, self.details).into_response()
+        if self.status_code == StatusCode::SERVICE_UNAVAILABLE {
+            (
+                self.status_code,
+                [(RETRY_AFTER, RETRY_AFTER_SECONDS.to_string())],
+                self.details,
+            )
+                .into_response()
+        } else {
+            (self.status_code, self.details).into_response()
+        }
     }
 }
 
@@ -36,6 +57,7 @@ impl From<GetFactError> for AppError {
     fn from(value: GetFactError) -> Self {
         let status_code = match value {
             GetFactError::NoSuchFact { id: _ } => StatusCode::NOT_FOUND,
+            GetFactError::Unavailable { inner: _ } => StatusCode::SERVICE_UNAVAILABLE,
             GetFactError::UnexpectedError { inner: _ } => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -50,6 +72,7 @@ impl From<GetRandomFactError> for AppError {
     fn from(value: GetRandomFactError) -> Self {
         let status_code = match value {
             GetRandomFactError::Empty => StatusCode::NOT_FOUND,
+            GetRandomFactError::Unavailable { inner: _ } => StatusCode::SERVICE_UNAVAILABLE,
             GetRandomFactError::UnexpectedError { inner: _ } => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -60,9 +83,88 @@ impl From<GetRandomFactError> for AppError {
     }
 }
 
+impl From<GetExtremesError> for AppError {
+    fn from(value: GetExtremesError) -> Self {
+        let status_code = match value {
+            GetExtremesError::Empty => StatusCode::NOT_FOUND,
+            GetExtremesError::Unavailable { inner: _ } => StatusCode::SERVICE_UNAVAILABLE,
+            GetExtremesError::UnexpectedError { inner: _ } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        Self {
+            status_code,
+            details: value.to_string(),
+        }
+    }
+}
+
+impl From<ListFactsError> for AppError {
+    fn from(value: ListFactsError) -> Self {
+        let status_code = match value {
+            ListFactsError::Unavailable { inner: _ } => StatusCode::SERVICE_UNAVAILABLE,
+            ListFactsError::UnexpectedError { inner: _ } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        Self {
+            status_code,
+            details: value.to_string(),
+        }
+    }
+}
+
+impl From<ListFactsRequestError> for AppError {
+    fn from(value: ListFactsRequestError) -> Self {
+        Self {
+            status_code: StatusCode::UNPROCESSABLE_ENTITY,
+            details: value.to_string(),
+        }
+    }
+}
+
+impl From<FindFactsRequestError> for AppError {
+    fn from(value: FindFactsRequestError) -> Self {
+        Self {
+            status_code: StatusCode::UNPROCESSABLE_ENTITY,
+            details: value.to_string(),
+        }
+    }
+}
+
+impl From<MigrationStatusError> for AppError {
+    fn from(value: MigrationStatusError) -> Self {
+        let status_code = match value {
+            MigrationStatusError::Unavailable { inner: _ } => StatusCode::SERVICE_UNAVAILABLE,
+            MigrationStatusError::UnexpectedError { inner: _ } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        Self {
+            status_code,
+            details: value.to_string(),
+        }
+    }
+}
+
+impl From<ResyncSequenceError> for AppError {
+    fn from(value: ResyncSequenceError) -> Self {
+        let status_code = match value {
+            ResyncSequenceError::Unavailable { inner: _ } => StatusCode::SERVICE_UNAVAILABLE,
+            ResyncSequenceError::UnexpectedError { inner: _ } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        Self {
+            status_code,
+            details: value.to_string(),
+        }
+    }
+}
+
 impl From<CreateFactError> for AppError {
     fn from(value: CreateFactError) -> Self {
         let status_code = match value {
+            CreateFactError::TitleTaken { title: _ } => StatusCode::PRECONDITION_FAILED,
+            CreateFactError::BodyTaken => StatusCode::CONFLICT,
+            CreateFactError::StorageFull { max: _ } => StatusCode::INSUFFICIENT_STORAGE,
+            CreateFactError::Unavailable { inner: _ } => StatusCode::SERVICE_UNAVAILABLE,
             CreateFactError::UnexpectedError { inner: _ } => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -73,10 +175,27 @@ impl From<CreateFactError> for AppError {
     }
 }
 
+impl From<UpdateFactError> for AppError {
+    fn from(value: UpdateFactError) -> Self {
+        let status_code = match value {
+            UpdateFactError::NoSuchFact { id: _ } => StatusCode::NOT_FOUND,
+            UpdateFactError::BodyTaken => StatusCode::CONFLICT,
+            UpdateFactError::Unavailable { inner: _ } => StatusCode::SERVICE_UNAVAILABLE,
+            UpdateFactError::UnexpectedError { inner: _ } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        Self {
+            status_code,
+            details: value.to_string(),
+        }
+    }
+}
+
 impl From<DeleteFactError> for AppError {
     fn from(value: DeleteFactError) -> Self {
         let status_code = match value {
             DeleteFactError::NoSuchFact { id: _ } => StatusCode::NOT_FOUND,
+            DeleteFactError::Unavailable { inner: _ } => StatusCode::SERVICE_UNAVAILABLE,
             DeleteFactError::UnexpectedError { inner: _ } => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -95,3 +214,42 @@ impl From<CreateFactRequestError> for AppError {
         }
     }
 }
+
+impl From<FactTagError> for AppError {
+    fn from(value: FactTagError) -> Self {
+        Self {
+            status_code: StatusCode::UNPROCESSABLE_ENTITY,
+            details: value.to_string(),
+        }
+    }
+}
+
+impl From<AddFactTagError> for AppError {
+    fn from(value: AddFactTagError) -> Self {
+        let status_code = match value {
+            AddFactTagError::NoSuchFact { id: _ } => StatusCode::NOT_FOUND,
+            AddFactTagError::Unavailable { inner: _ } => StatusCode::SERVICE_UNAVAILABLE,
+            AddFactTagError::UnexpectedError { inner: _ } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        Self {
+            status_code,
+            details: value.to_string(),
+        }
+    }
+}
+
+impl From<RemoveFactTagError> for AppError {
+    fn from(value: RemoveFactTagError) -> Self {
+        let status_code = match value {
+            RemoveFactTagError::NoSuchTag { id: _, tag: _ } => StatusCode::NOT_FOUND,
+            RemoveFactTagError::Unavailable { inner: _ } => StatusCode::SERVICE_UNAVAILABLE,
+            RemoveFactTagError::UnexpectedError { inner: _ } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        Self {
+            status_code,
+            details: value.to_string(),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt::Write;
+
+use crate::facts::repository::Fact;
+
+pub const RSS_CONTENT_TYPE: &str = "application/rss+xml";
+
+const CHANNEL_TITLE: &str = "Facts";
+const CHANNEL_DESCRIPTION: &str = env!("CARGO_PKG_DESCRIPTION");
+
+pub fn escape(raw: &str) -> String {
+    let mut escaped = String::with_capacity(raw.len());
+
+    for c in raw.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+pub fn permalink(public_url: &str, fact: &Fact) -> String {
+    format!(
+        "{}/api/facts/{}",
+        public_url.trim_end_matches('/'),
+        fact.id()
+    )
+}
+
+pub fn render_rss(public_url: &str, facts: &[Fact]) -> String {
+    let mut feed = String::new();
+
+    feed.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    feed.push_str(r#"<rss version="2.0"><channel>"#);
+    let _ = write!(
+        feed,
+        "<title>{}</title><link>{}</link><description>{}</description>",
+        escape(CHANNEL_TITLE),
+        escape(public_url),
+        escape(CHANNEL_DESCRIPTION),
+    );
+
+    for fact in facts {
+        let link = escape(&permalink(public_url, fact));
+        let _ = write!(
+            feed,
+            r#"<item><title>{}</title><link>{link}</link><description>{}</description><guid isPermaLink="true">{link}</guid><pubDate>{}</pubDate></item>"#,
+            escape(&String::from(fact.title().to_owned())),
+            escape(&String::from(fact.body().to_owned())),
+            fact.created_at().to_rfc2822(),
+        );
+    }
+
+    feed.push_str("</channel></rss>");
+    feed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escaping() {
+        assert_eq!(
+            escape(r#"<a href="x">Tom & 'Jerry'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &apos;Jerry&apos;&lt;/a&gt;"
+        );
+    }
+}
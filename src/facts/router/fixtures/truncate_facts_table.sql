@@ -1 +1 @@
-TRUNCATE facts
\ No newline at end of file
+TRUNCATE facts CASCADE
\ No newline at end of file
@@ -0,0 +1,683 @@
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{
+        header::{ACCEPT, CONTENT_TYPE},
+        request::Parts,
+        HeaderValue,
+        StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::DateTime;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use serde_json::{to_value, Map, Value};
+
+use super::{
+    errors::AppError,
+    models::{HttpFactResponse, JsonApiDocument, JsonApiFactResource},
+    state::AppState,
+};
+use crate::facts::repository::Fact;
+
+pub const JSON_API_CONTENT_TYPE: &str = "application/vnd.api+json";
+pub const KEY_VALUE_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+const KEY_VALUE_LINE_LENGTH: usize = 75;
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseFormat {
+    #[default]
+    Json,
+    JsonApi,
+    KeyValue,
+}
+
+#[derive(Deserialize)]
+struct ResponseFormatQuery {
+    format: Option<ResponseFormat>,
+}
+
+const FACT_FIELDS: [&str; 6] = [
+    "id",
+    "title",
+    "body",
+    "created_at",
+    "created_by",
+    "updated_by",
+];
+const TIMESTAMP_FIELDS: [&str; 2] = ["created_at", "updated_at"];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fields {
+    names: Option<Vec<String>>,
+    tz: Option<Tz>,
+    actors: bool,
+}
+
+#[derive(Deserialize)]
+struct FieldsQuery {
+    fields: Option<String>,
+    tz: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FactEnvelope<T, M> {
+    fact: T,
+    #[serde(flatten)]
+    meta: M,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Selected<T> {
+    Full(T),
+    Partial(Map<String, Value>),
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ResponseFormat {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let Query(query) =
+            Query::<ResponseFormatQuery>::try_from_uri(&parts.uri).map_err(|err| AppError {
+                status_code: StatusCode::BAD_REQUEST,
+                details: err.body_text(),
+            })?;
+
+        if let Some(format) = query.format {
+            return Ok(format);
+        }
+
+        let accepts_json_api = parts
+            .headers
+            .get_all(ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|media_range| media_range.split(';').next())
+            .any(|media_type| {
+                media_type
+                    .trim()
+                    .eq_ignore_ascii_case(JSON_API_CONTENT_TYPE)
+            });
+
+        if accepts_json_api {
+            Ok(Self::JsonApi)
+        } else {
+            Ok(Self::Json)
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for Fields {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let Query(query) =
+            Query::<FieldsQuery>::try_from_uri(&parts.uri).map_err(|err| AppError {
+                status_code: StatusCode::BAD_REQUEST,
+                details: err.body_text(),
+            })?;
+        let tz = query
+            .tz
+            .map(|raw| {
+                raw.parse::<Tz>().map_err(|_| AppError {
+                    status_code: StatusCode::BAD_REQUEST,
+                    details: format!("Unknown time zone {raw:?}, expected an IANA name"),
+                })
+            })
+            .transpose()?;
+        let actors = state.expose_actors;
+        let Some(raw) = query.fields else {
+            return Ok(Self {
+                names: None,
+                tz,
+                actors,
+            });
+        };
+
+        let mut fields = Vec::new();
+        for field in raw.split(',').map(str::trim) {
+            if !FACT_FIELDS.contains(&field) {
+                return Err(AppError {
+                    status_code: StatusCode::BAD_REQUEST,
+                    details: format!(
+                        "Unknown field {field:?}, expected some of {}",
+                        FACT_FIELDS.join(", ")
+                    ),
+                });
+            }
+            if !fields.iter().any(|known| known == field) {
+                fields.push(field.to_owned());
+            }
+        }
+
+        Ok(Self {
+            names: Some(fields),
+            tz,
+            actors,
+        })
+    }
+}
+
+impl Fields {
+    pub fn with_actors(mut self, actors: bool) -> Self {
+        self.actors = actors;
+        self
+    }
+
+    fn retain(&self, object: &mut Map<String, Value>) {
+        if let Some(fields) = &self.names {
+            object.retain(|key, _| fields.contains(key));
+        }
+    }
+
+    fn localize(&self, object: &mut Map<String, Value>) {
+        let Some(tz) = self.tz else {
+            return;
+        };
+
+        for field in TIMESTAMP_FIELDS {
+            if let Some(Value::String(raw)) = object.get_mut(field) {
+                if let Ok(timestamp) = DateTime::parse_from_rfc3339(raw) {
+                    *raw = timestamp.with_timezone(&tz).to_rfc3339();
+                }
+            }
+        }
+    }
+
+    fn select<T: Serialize>(&self, value: T, path: Option<&str>) -> Selected<T> {
+        if self.names.is_none() && self.tz.is_none() {
+            return Selected::Full(value);
+        }
+
+        let Ok(Value::Object(mut object)) = to_value(value) else {
+            return Selected::Partial(Map::new());
+        };
+        let selected = match path {
+            Some(path) => match object.get_mut(path) {
+                Some(Value::Object(nested)) => Some(nested),
+                _ => None,
+            },
+            None => Some(&mut object),
+        };
+        if let Some(selected) = selected {
+            self.retain(selected);
+            self.localize(selected);
+        }
+
+        Selected::Partial(object)
+    }
+
+    fn json(&self, fact: &Fact) -> Selected<HttpFactResponse> {
+        self.select(HttpFactResponse::new(fact, self.actors), None)
+    }
+
+    fn json_api(&self, fact: &Fact) -> Selected<JsonApiFactResource> {
+        self.select(
+            JsonApiFactResource::from(HttpFactResponse::new(fact, self.actors)),
+            Some("attributes"),
+        )
+    }
+
+    fn key_value<M: Serialize>(&self, fact: &Fact, meta: M) -> String {
+        let mut object = match to_value(self.json(fact)) {
+            Ok(Value::Object(object)) => object,
+            _ => Map::new(),
+        };
+        if let Ok(Value::Object(meta)) = to_value(meta) {
+            object.extend(meta);
+        }
+
+        let mut record = String::from("BEGIN:FACT\r\n");
+        for field in FACT_FIELDS {
+            if let Some(value) = object.remove(field) {
+                push_key_value(&mut record, field, &value);
+            }
+        }
+        for (key, value) in &object {
+            push_key_value(&mut record, key, value);
+        }
+        record.push_str("END:FACT\r\n");
+
+        record
+    }
+}
+
+fn push_key_value(record: &mut String, key: &str, value: &Value) {
+    let value = match value {
+        Value::String(raw) => raw
+            .replace('\\', "\\\\")
+            .replace('\r', "\\r")
+            .replace('\n', "\\n"),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    };
+
+    let mut length = 0;
+    for c in format!("{}:{value}", key.to_uppercase()).chars() {
+        if length + c.len_utf8() > KEY_VALUE_LINE_LENGTH {
+            record.push_str("\r\n ");
+            length = 1;
+        }
+        record.push(c);
+        length += c.len_utf8();
+    }
+    record.push_str("\r\n");
+}
+
+impl ResponseFormat {
+    pub fn fact(self, status_code: StatusCode, fact: &Fact, fields: &Fields) -> Response {
+        match self {
+            Self::Json => (status_code, Json(fields.json(fact))).into_response(),
+            Self::JsonApi => (
+                status_code,
+                [(CONTENT_TYPE, JSON_API_CONTENT_TYPE)],
+                Json(JsonApiDocument::new(fields.json_api(fact))),
+            )
+                .into_response(),
+            Self::KeyValue => (
+                status_code,
+                [(CONTENT_TYPE, KEY_VALUE_CONTENT_TYPE)],
+                fields.key_value(fact, ()),
+            )
+                .into_response(),
+        }
+    }
+
+    pub fn fact_with_meta<M: Serialize>(
+        self,
+        status_code: StatusCode,
+        fact: &Fact,
+        fields: &Fields,
+        meta: M,
+    ) -> Response {
+        match self {
+            Self::Json => (
+                status_code,
+                Json(FactEnvelope {
+                    fact: fields.json(fact),
+                    meta,
+                }),
+            )
+                .into_response(),
+            Self::JsonApi => (
+                status_code,
+                [(CONTENT_TYPE, JSON_API_CONTENT_TYPE)],
+                Json(JsonApiDocument::new(fields.json_api(fact)).with_meta(meta)),
+            )
+                .into_response(),
+            Self::KeyValue => (
+                status_code,
+                [(CONTENT_TYPE, KEY_VALUE_CONTENT_TYPE)],
+                fields.key_value(fact, meta),
+            )
+                .into_response(),
+        }
+    }
+
+    pub fn facts(self, status_code: StatusCode, facts: Vec<Fact>, fields: &Fields) -> Response {
+        match self {
+            Self::Json => (
+                status_code,
+                Json(
+                    facts
+                        .into_iter()
+                        .map(|fact| fields.json(&fact))
+                        .collect::<Vec<_>>(),
+                ),
+            )
+                .into_response(),
+            Self::JsonApi => (
+                status_code,
+                [(CONTENT_TYPE, JSON_API_CONTENT_TYPE)],
+                Json(JsonApiDocument::new(
+                    facts
+                        .into_iter()
+                        .map(|fact| fields.json_api(&fact))
+                        .collect::<Vec<_>>(),
+                )),
+            )
+                .into_response(),
+            Self::KeyValue => (
+                status_code,
+                [(CONTENT_TYPE, KEY_VALUE_CONTENT_TYPE)],
+                facts
+                    .into_iter()
+                    .map(|fact| fields.key_value(&fact, ()))
+                    .collect::<String>(),
+            )
+                .into_response(),
+        }
+    }
+
+    pub fn page(
+        self,
+        status_code: StatusCode,
+        facts: Vec<Fact>,
+        total: u64,
+        fields: &Fields,
+    ) -> Response {
+        let mut response = match self {
+            Self::Json | Self::KeyValue => self.facts(status_code, facts, fields),
+            Self::JsonApi => (
+                status_code,
+                [(CONTENT_TYPE, JSON_API_CONTENT_TYPE)],
+                Json(
+                    JsonApiDocument::new(
+                        facts
+                            .into_iter()
+                            .map(|fact| fields.json_api(&fact))
+                            .collect::<Vec<_>>(),
+                    )
+                    .with_total(total),
+                ),
+            )
+                .into_response(),
+        };
+
+        response
+            .headers_mut()
+            .insert(TOTAL_COUNT_HEADER, HeaderValue::from(total));
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::Request, Router};
+    use http_body_util::BodyExt;
+    use serde_json::{from_slice, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::facts::{
+        repository::{FactBody, FactTitle},
+        AppRouter,
+        AppState,
+        FactId,
+    };
+
+    fn parse_key_value(record: &str) -> Vec<(String, String)> {
+        record
+            .replace("\r\n ", "")
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| {
+                let mut unescaped = String::new();
+                let mut chars = value.chars();
+                while let Some(c) = chars.next() {
+                    if c != '\\' {
+                        unescaped.push(c);
+                        continue;
+                    }
+                    match chars.next() {
+                        Some('n') => unescaped.push('\n'),
+                        Some('r') => unescaped.push('\r'),
+                        Some(other) => unescaped.push(other),
+                        None => {}
+                    }
+                }
+                (key.to_owned(), unescaped)
+            })
+            .collect()
+    }
+
+    async fn call(request: Request<Body>) -> (StatusCode, Option<String>, Value) {
+        let state = AppState::default();
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router.with_state(state).oneshot(request).await.unwrap();
+
+        let status = raw_response.status();
+        let content_type = raw_response
+            .headers()
+            .get(CONTENT_TYPE)
+            .map(|value| value.to_str().unwrap().to_owned());
+        let body =
+            from_slice::<Value>(&raw_response.into_body().collect().await.unwrap().to_bytes())
+                .unwrap();
+
+        (status, content_type, body)
+    }
+
+    #[tokio::test]
+    async fn single_fact() {
+        let (status, content_type, body) = call(
+            Request::get("/42?format=jsonapi")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some(JSON_API_CONTENT_TYPE));
+        assert_eq!(body["data"]["type"], "facts");
+        assert_eq!(body["data"]["id"], "42");
+        assert!(body["data"]["attributes"]["title"].is_string());
+        assert!(body["data"]["attributes"]["body"].is_string());
+        assert!(body["data"]["attributes"].get("id").is_none());
+    }
+
+    #[tokio::test]
+    async fn list_of_facts() {
+        let (status, content_type, body) = call(
+            Request::get("/")
+                .header(ACCEPT, JSON_API_CONTENT_TYPE)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some(JSON_API_CONTENT_TYPE));
+
+        let data = body["data"].as_array().unwrap();
+
+        assert!(!data.is_empty());
+        assert!(data.iter().all(|resource| resource["type"] == "facts"));
+    }
+
+    #[tokio::test]
+    async fn plain_json_by_default() {
+        let (status, content_type, body) =
+            call(Request::get("/42").body(Body::empty()).unwrap()).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some("application/json"));
+        assert_eq!(body["id"], 42);
+    }
+
+    #[tokio::test]
+    async fn selected_fields() {
+        let (status, _, body) = call(
+            Request::get("/42?fields=id,title")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["id"], 42);
+        assert!(body["title"].is_string());
+        assert!(body.get("body").is_none());
+        assert!(body.get("created_at").is_none());
+
+        let (status, _, body) = call(
+            Request::get("/?format=jsonapi&fields=title")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+
+        let attributes = body["data"][0]["attributes"].as_object().unwrap();
+
+        assert_eq!(body["data"][0]["id"], "42");
+        assert_eq!(attributes.keys().collect::<Vec<_>>(), ["title"]);
+    }
+
+    #[tokio::test]
+    async fn unknown_field() {
+        let state = AppState::default();
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::get("/42?fields=id,author")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::BAD_REQUEST);
+
+        let body = raw_response.into_body().collect().await.unwrap().to_bytes();
+
+        assert!(String::from_utf8_lossy(&body).contains("author"));
+    }
+
+    #[tokio::test]
+    async fn timezone() {
+        let (status, _, body) = call(
+            Request::get("/42?tz=Asia/Kolkata")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["created_at"], "1970-01-01T05:30:00+05:30");
+
+        let (status, _, body) = call(
+            Request::get("/42?format=jsonapi&fields=created_at&tz=America/New_York")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["data"]["attributes"]["created_at"],
+            "1969-12-31T19:00:00-05:00"
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_timezone() {
+        let state = AppState::default();
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::get("/42?tz=Mars/Olympus_Mons")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::BAD_REQUEST);
+
+        let body = raw_response.into_body().collect().await.unwrap().to_bytes();
+
+        assert!(String::from_utf8_lossy(&body).contains("Mars/Olympus_Mons"));
+    }
+
+    #[tokio::test]
+    async fn unknown_format() {
+        let state = AppState::default();
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(Request::get("/42?format=xml").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn key_value_folds_long_body() {
+        let body = "Octopuses have three hearts.\nTwo pump blood to the gills, \\ one to the \
+                    rest of the body — and the latter stops while they swim. "
+            .repeat(4);
+        let fact = Fact::new(
+            FactId::new(7).unwrap(),
+            &FactTitle::new("Octopus").unwrap(),
+            &FactBody::new(&body).unwrap(),
+            DateTime::UNIX_EPOCH,
+        );
+
+        let record = Fields::default().key_value(&fact, ());
+
+        assert!(record.starts_with("BEGIN:FACT\r\nID:7\r\nTITLE:Octopus\r\n"));
+        assert!(record.ends_with("END:FACT\r\n"));
+        assert!(record.contains("\r\n "));
+        assert!(record
+            .split("\r\n")
+            .all(|line| line.len() <= KEY_VALUE_LINE_LENGTH));
+        assert!(parse_key_value(&record).contains(&("BODY".to_owned(), body)));
+    }
+
+    #[tokio::test]
+    async fn key_value() {
+        let state = AppState::default();
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::get("/42?format=keyvalue&fields=id,title")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+        assert_eq!(raw_response.headers()[CONTENT_TYPE], KEY_VALUE_CONTENT_TYPE);
+
+        let body = raw_response.into_body().collect().await.unwrap().to_bytes();
+        let fields = parse_key_value(&String::from_utf8_lossy(&body));
+
+        assert_eq!(
+            fields
+                .iter()
+                .map(|(key, _)| key.as_str())
+                .collect::<Vec<_>>(),
+            ["BEGIN", "ID", "TITLE", "END"]
+        );
+        assert_eq!(fields[1].1, "42");
+    }
+
+    #[tokio::test]
+    async fn list_total() {
+        let state = AppState::default();
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::get("/?format=jsonapi")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.headers()[TOTAL_COUNT_HEADER], "1");
+
+        let body =
+            from_slice::<Value>(&raw_response.into_body().collect().await.unwrap().to_bytes())
+                .unwrap();
+
+        assert_eq!(body["meta"]["total"], 1);
+    }
+}
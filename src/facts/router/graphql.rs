@@ -0,0 +1,313 @@
+use async_graphql::{Context, EmptySubscription, ErrorExtensions, Object, Schema, SimpleObject};
+use axum::{
+    debug_handler,
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::post,
+    Extension,
+    Json,
+    Router,
+};
+use axum_extra::{
+    headers::{authorization::Basic, Authorization},
+    TypedHeader,
+};
+use chrono::{DateTime, Utc};
+
+use super::{
+    errors::AppError,
+    handlers::{audit_write, verify_credentials},
+    models::Actor,
+    service,
+    state::AppState,
+};
+use crate::facts::repository::{
+    CreateFactRequestError,
+    DeleteFactError,
+    Fact,
+    FactId,
+    FactTitle,
+    ListFactsRequest,
+};
+
+type FactsSchema = Schema<Query, Mutation, EmptySubscription>;
+
+pub struct GraphQLRouter {
+    state: AppState,
+}
+
+impl GraphQLRouter {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Fact")]
+struct GraphQLFact {
+    id: i32,
+    title: String,
+    body: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<Fact> for GraphQLFact {
+    fn from(value: Fact) -> Self {
+        Self {
+            id: value.id().into(),
+            title: value.title().to_owned().into(),
+            body: value.body().to_owned().into(),
+            created_at: value.created_at(),
+        }
+    }
+}
+
+impl From<AppError> for async_graphql::Error {
+    fn from(value: AppError) -> Self {
+        let status = value.status_code.as_u16();
+
+        Self::new(value.details).extend_with(|_, extensions| extensions.set("status", status))
+    }
+}
+
+fn actor<'a>(ctx: &Context<'a>) -> Result<&'a Actor, AppError> {
+    ctx.data_opt::<Actor>().ok_or(AppError {
+        status_code: StatusCode::UNAUTHORIZED,
+        details: "Auth failed: Missing basic credentials".to_owned(),
+    })
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    async fn fact(&self, ctx: &Context<'_>, id: i32) -> async_graphql::Result<GraphQLFact> {
+        let state = ctx.data::<AppState>()?;
+        let id = FactId::new(id).map_err(AppError::from)?;
+        let fact = state
+            .facts
+            .get(id, actor(ctx).is_err())
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(fact.into())
+    }
+
+    async fn random_fact(&self, ctx: &Context<'_>) -> async_graphql::Result<GraphQLFact> {
+        let state = ctx.data::<AppState>()?;
+        if state.disable_random {
+            return Err(service::disabled("Random fact").into());
+        }
+        let fact = if actor(ctx).is_ok() {
+            state.facts.get_random().await
+        } else {
+            state.facts.get_random_public(usize::MAX).await
+        };
+
+        Ok(fact.map_err(AppError::from)?.into())
+    }
+
+    async fn facts(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<Vec<GraphQLFact>> {
+        let state = ctx.data::<AppState>()?;
+        let request = ListFactsRequest::new(
+            limit.unwrap_or(ListFactsRequest::DEFAULT_LIMIT),
+            offset.unwrap_or_default(),
+        )
+        .map_err(AppError::from)?
+        .with_public_only(actor(ctx).is_err());
+        let facts = state.facts.list(&request).await.map_err(AppError::from)?;
+
+        Ok(facts.into_iter().map(GraphQLFact::from).collect())
+    }
+}
+
+pub struct Mutation;
+
+#[Object]
+impl Mutation {
+    async fn create_fact(
+        &self,
+        ctx: &Context<'_>,
+        title: String,
+        body: String,
+        public: Option<bool>,
+    ) -> async_graphql::Result<GraphQLFact> {
+        let state = ctx.data::<AppState>()?;
+        if !state.endpoints.create {
+            return Err(service::disabled("Creating facts").into());
+        }
+        let actor = actor(ctx)?;
+        let title = FactTitle::new(&title)
+            .map_err(CreateFactRequestError::from)
+            .map_err(AppError::from)?;
+        let result =
+            service::create_fact(state, actor, &title, &body, public.unwrap_or(true), false)
+                .await?;
+
+        Ok(result.into())
+    }
+
+    async fn delete_fact(&self, ctx: &Context<'_>, id: i32) -> async_graphql::Result<bool> {
+        let state = ctx.data::<AppState>()?;
+        if !state.endpoints.delete {
+            return Err(service::disabled("Deleting facts").into());
+        }
+        let actor = actor(ctx)?;
+        let id = FactId::new(id).map_err(AppError::from)?;
+        match state.facts.delete(id).await {
+            Ok(()) => audit_write(state, actor, id, "deleted"),
+            Err(DeleteFactError::NoSuchFact { id: _ }) if state.idempotent_delete => {}
+            Err(err) => return Err(AppError::from(err).into()),
+        }
+
+        Ok(true)
+    }
+}
+
+#[debug_handler]
+pub async fn graphql(
+    State(state): State<AppState>,
+    Extension(schema): Extension<FactsSchema>,
+    auth: Option<TypedHeader<Authorization<Basic>>>,
+    Json(request): Json<async_graphql::Request>,
+) -> Result<impl IntoResponse, AppError> {
+    let request = match auth {
+        Some(TypedHeader(auth)) => {
+            verify_credentials(&state, &auth)?;
+            request.data(Actor::new(auth.username()))
+        }
+        None => request,
+    };
+
+    Ok(Json(schema.execute(request).await))
+}
+
+impl From<GraphQLRouter> for Router<AppState> {
+    fn from(graphql_router: GraphQLRouter) -> Self {
+        let schema = Schema::build(Query, Mutation, EmptySubscription)
+            .data(graphql_router.state)
+            .finish();
+
+        Router::new()
+            .route("/", post(graphql))
+            .layer(Extension(schema))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use reqwest::{header::CONTENT_TYPE, Method};
+    use serde_json::{from_slice, json, Value};
+    use sqlx::{query_scalar, PgPool};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::facts::{router::Endpoints, SqlxFactsRepository};
+
+    async fn execute(state: AppState, query: &str) -> Value {
+        let router: Router<AppState> = GraphQLRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(json!({ "query": query }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        from_slice(&raw_response.into_body().collect().await.unwrap().to_bytes()).unwrap()
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn fact(pool: PgPool) {
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            "Octopuses",
+            "Octopuses have three hearts"
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let response = execute(
+            state.clone(),
+            &format!("{{ fact(id: {id}) {{ id title body }} }}"),
+        )
+        .await;
+
+        assert_eq!(
+            response["data"]["fact"],
+            json!({ "id": id, "title": "Octopuses", "body": "Octopuses have three hearts" })
+        );
+
+        let response = execute(state, "{ fact(id: 0) { id } }").await;
+
+        assert_eq!(response["data"], Value::Null);
+        assert_eq!(response["errors"][0]["extensions"]["status"], 422);
+    }
+
+    #[tokio::test]
+    async fn mutations_require_credentials() {
+        let response = execute(
+            AppState::default(),
+            r#"mutation { createFact(title: "Title", body: "Body") { id } }"#,
+        )
+        .await;
+
+        assert_eq!(response["errors"][0]["extensions"]["status"], 401);
+    }
+
+    #[tokio::test]
+    async fn disabled_operations() {
+        let state = AppState {
+            disable_random: true,
+            endpoints: Endpoints {
+                create: false,
+                delete: false,
+                search: true,
+            },
+            ..Default::default()
+        };
+
+        for (query, message) in [
+            (
+                r#"mutation { createFact(title: "Title", body: "Body") { id } }"#,
+                "Creating facts is disabled",
+            ),
+            (
+                "mutation { deleteFact(id: 42) }",
+                "Deleting facts is disabled",
+            ),
+            ("{ randomFact { id } }", "Random fact is disabled"),
+        ] {
+            let response = execute(state.clone(), query).await;
+
+            assert_eq!(response["data"], Value::Null, "{query}");
+            assert_eq!(response["errors"][0]["message"], message);
+            assert_eq!(response["errors"][0]["extensions"]["status"], 404);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::net::SocketAddr;
+
+use axum::http::StatusCode;
+use axum_extra::headers::{authorization::Basic, Authorization, HeaderMapExt};
+use tonic::{transport::Server, Code, Request, Response, Status};
+
+use super::{
+    errors::AppError,
+    handlers::{audit_write, verify_credentials},
+    models::Actor,
+    service,
+    state::AppState,
+};
+use crate::facts::repository::{CreateFactRequestError, DeleteFactError, Fact, FactId, FactTitle};
+
+mod proto {
+    #![allow(clippy::pedantic)]
+    tonic::include_proto!("facts");
+}
+
+use proto::facts_server::{Facts, FactsServer};
+
+pub struct GrpcFacts {
+    state: AppState,
+}
+
+impl GrpcFacts {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    pub async fn serve(self, address: SocketAddr) -> Result<(), tonic::transport::Error> {
+        Server::builder()
+            .add_service(FactsServer::new(self))
+            .serve(address)
+            .await
+    }
+
+    fn authenticate<T>(&self, request: &Request<T>) -> Result<Actor, AppError> {
+        let auth = request
+            .metadata()
+            .clone()
+            .into_headers()
+            .typed_get::<Authorization<Basic>>()
+            .ok_or(AppError {
+                status_code: StatusCode::UNAUTHORIZED,
+                details: "Auth failed: Missing basic credentials".to_owned(),
+            })?;
+        verify_credentials(&self.state, &auth)?;
+
+        Ok(Actor::new(auth.username()))
+    }
+}
+
+impl From<Fact> for proto::Fact {
+    fn from(value: Fact) -> Self {
+        Self {
+            id: value.id().into(),
+            title: value.title().to_owned().into(),
+            body: value.body().to_owned().into(),
+            created_at: value.created_at().to_rfc3339(),
+        }
+    }
+}
+
+impl From<AppError> for Status {
+    fn from(value: AppError) -> Self {
+        let code = match value.status_code {
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Code::InvalidArgument,
+            StatusCode::UNAUTHORIZED => Code::Unauthenticated,
+            StatusCode::FORBIDDEN => Code::PermissionDenied,
+            StatusCode::NOT_FOUND => Code::NotFound,
+            StatusCode::CONFLICT => Code::AlreadyExists,
+            StatusCode::INSUFFICIENT_STORAGE => Code::ResourceExhausted,
+            StatusCode::SERVICE_UNAVAILABLE => Code::Unavailable,
+            _ => Code::Internal,
+        };
+
+        Status::new(code, value.details)
+    }
+}
+
+#[tonic::async_trait]
+impl Facts for GrpcFacts {
+    async fn get_fact(
+        &self,
+        request: Request<proto::GetFactRequest>,
+    ) -> Result<Response<proto::Fact>, Status> {
+        let public_only = self.authenticate(&request).is_err();
+        let id = FactId::new(request.into_inner().id).map_err(AppError::from)?;
+        let fact = self
+            .state
+            .facts
+            .get(id, public_only)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(Response::new(fact.into()))
+    }
+
+    async fn get_random_fact(
+        &self,
+        request: Request<proto::GetRandomFactRequest>,
+    ) -> Result<Response<proto::Fact>, Status> {
+        if self.state.disable_random {
+            return Err(Status::unimplemented("Random fact is disabled"));
+        }
+        let fact = if self.authenticate(&request).is_ok() {
+            self.state.facts.get_random().await
+        } else {
+            self.state.facts.get_random_public(usize::MAX).await
+        };
+
+        Ok(Response::new(fact.map_err(AppError::from)?.into()))
+    }
+
+    async fn create_fact(
+        &self,
+        request: Request<proto::CreateFactRequest>,
+    ) -> Result<Response<proto::Fact>, Status> {
+        if !self.state.endpoints.create {
+            return Err(Status::unimplemented("Creating facts is disabled"));
+        }
+        let actor = self.authenticate(&request)?;
+        let request = request.into_inner();
+        let title = FactTitle::new(&request.title)
+            .map_err(CreateFactRequestError::from)
+            .map_err(AppError::from)?;
+        let result = service::create_fact(
+            &self.state,
+            &actor,
+            &title,
+            &request.body,
+            request.public.unwrap_or(true),
+            false,
+        )
+        .await?;
+
+        Ok(Response::new(result.into()))
+    }
+
+    async fn delete_fact(
+        &self,
+        request: Request<proto::DeleteFactRequest>,
+    ) -> Result<Response<proto::DeleteFactResponse>, Status> {
+        if !self.state.endpoints.delete {
+            return Err(Status::unimplemented("Deleting facts is disabled"));
+        }
+        let actor = self.authenticate(&request)?;
+        let id = FactId::new(request.into_inner().id).map_err(AppError::from)?;
+        match self.state.facts.delete(id).await {
+            Ok(()) => audit_write(&self.state, &actor, id, "deleted"),
+            Err(DeleteFactError::NoSuchFact { id: _ }) if self.state.idempotent_delete => {}
+            Err(err) => return Err(AppError::from(err).into()),
+        }
+
+        Ok(Response::new(proto::DeleteFactResponse {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper_util::rt::TokioIo;
+    use tokio::io::duplex;
+    use tonic::transport::{Endpoint, Uri};
+    use tower::service_fn;
+
+    use super::{proto::facts_client::FactsClient, *};
+    use crate::facts::router::Endpoints;
+
+    async fn client(state: AppState) -> FactsClient<tonic::transport::Channel> {
+        let (client_io, server_io) = duplex(1024);
+
+        tokio::spawn(async move {
+            Server::builder()
+                .add_service(FactsServer::new(GrpcFacts::new(state)))
+                .serve_with_incoming(tokio_stream::once(Ok::<_, std::io::Error>(server_io)))
+                .await
+        });
+
+        let mut client_io = Some(client_io);
+        let channel = Endpoint::from_static("http://localhost")
+            .connect_with_connector(service_fn(move |_: Uri| {
+                let client_io = client_io.take();
+                async move {
+                    client_io
+                        .map(TokioIo::new)
+                        .ok_or(std::io::Error::other("Client is already taken"))
+                }
+            }))
+            .await
+            .unwrap();
+
+        FactsClient::new(channel)
+    }
+
+    #[tokio::test]
+    async fn get_fact() {
+        let mut client = client(AppState::default()).await;
+
+        let fact = client
+            .get_fact(proto::GetFactRequest { id: 7 })
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(fact.id, 7);
+        assert_eq!(fact.created_at, "1970-01-01T00:00:00+00:00");
+
+        let status = client
+            .get_fact(proto::GetFactRequest { id: 0 })
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn disabled_operations() {
+        let mut client = client(AppState {
+            disable_random: true,
+            endpoints: Endpoints {
+                create: false,
+                delete: false,
+                search: true,
+            },
+            ..Default::default()
+        })
+        .await;
+
+        let statuses = [
+            client
+                .get_random_fact(proto::GetRandomFactRequest {})
+                .await
+                .unwrap_err(),
+            client
+                .create_fact(proto::CreateFactRequest {
+                    title: "Title".to_owned(),
+                    body: "Body".to_owned(),
+                    public: None,
+                })
+                .await
+                .unwrap_err(),
+            client
+                .delete_fact(proto::DeleteFactRequest { id: 42 })
+                .await
+                .unwrap_err(),
+        ];
+
+        for status in statuses {
+            assert_eq!(status.code(), Code::Unimplemented, "{}", status.message());
+        }
+    }
+}
@@ -1,25 +1,84 @@
+use std::{convert::Infallible, sync::atomic::Ordering};
+
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
 use axum::{
     debug_handler,
-    extract::{Path, Request, State},
-    http::StatusCode,
-    middleware::{from_fn_with_state, Next},
-    response::IntoResponse,
-    routing::{delete, get, post},
+    extract::{FromRequestParts, Path, Query, Request, State},
+    http::{
+        header::{CONTENT_TYPE, LOCATION},
+        request::Parts,
+        HeaderValue,
+        StatusCode,
+    },
+    middleware::{from_fn, from_fn_with_state, Next},
+    response::{
+        sse::{Event, KeepAlive},
+        IntoResponse,
+        Sse,
+    },
+    routing::{delete, get, patch, post},
+    Extension,
     Json,
     Router,
 };
 use axum_extra::{
-    headers::{authorization::Basic, Authorization},
+    headers::{authorization::Basic, Authorization, IfNoneMatch},
     TypedHeader,
 };
+use chrono::{Datelike, NaiveDate, Utc};
+use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+use tracing::info;
 
 use super::{
+    card::{render_card, SVG_CONTENT_TYPE},
     errors::AppError,
-    models::{HttpCreateFactRequestBody, HttpFactResponse},
+    feed::{permalink, render_rss, RSS_CONTENT_TYPE},
+    format::{Fields, ResponseFormat},
+    models::{
+        Actor,
+        Audience,
+        FeedToken,
+        HttpAddFactTagRequestBody,
+        HttpChangesQuery,
+        HttpCountByDayQuery,
+        HttpCreateFactRequestBody,
+        HttpDayCountResponse,
+        HttpDetailedHealthResponse,
+        HttpDigestResponse,
+        HttpExtremesResponse,
+        HttpFeedQuery,
+        HttpFeedResponse,
+        HttpFindFactsRequestBody,
+        HttpLikesResponse,
+        HttpLimitsResponse,
+        HttpListFactsQuery,
+        HttpMergePatchFactBody,
+        HttpRandomFactMeta,
+        HttpRandomQuery,
+        HttpRecentQuery,
+        HttpTagCountResponse,
+        JsonLdFact,
+        Pagination,
+        RandomBias,
+    },
+    service,
     state::AppState,
+    timing::{server_timing_middleware, timed},
+};
+use crate::facts::repository::{
+    DeleteFactError,
+    FactId,
+    FactTag,
+    FindFactsRequest,
+    GetRandomFactError,
 };
-use crate::facts::repository::{CreateFactRequest, FactId};
+
+const FEED_SIZE: u32 = 20;
+const JSON_CONTENT_TYPE: &str = "application/json";
+const JSON_LD_CONTENT_TYPE: &str = "application/ld+json";
+const MERGE_PATCH_CONTENT_TYPE: &str = "application/merge-patch+json";
+const RESULTS_TRUNCATED_HEADER: &str = "x-results-truncated";
+const TRACING_AUDIT_TARGET: &str = "facts::audit";
 
 pub struct AppRouter {
     state: AppState,
@@ -35,57 +94,588 @@ impl AppRouter {
 pub async fn get_fact(
     Path(id): Path<i32>,
     State(state): State<AppState>,
+    audience: Audience,
+    format: ResponseFormat,
+    fields: Fields,
+) -> Result<impl IntoResponse, AppError> {
+    let id = FactId::new(id)?;
+    let (result, timing) = timed(state.facts.get(id, audience == Audience::Anonymous)).await;
+
+    Ok((timing, format.fact(StatusCode::OK, &result?, &fields)))
+}
+
+#[debug_handler]
+pub async fn get_fact_json_ld(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    audience: Audience,
+) -> Result<impl IntoResponse, AppError> {
+    let id = FactId::new(id)?;
+    let (fact, timing) = timed(state.facts.get(id, audience == Audience::Anonymous)).await;
+    let fact = fact?;
+    let url = permalink(&state.public_url, &fact);
+
+    Ok((
+        StatusCode::OK,
+        timing,
+        [(CONTENT_TYPE, JSON_LD_CONTENT_TYPE)],
+        Json(JsonLdFact::new(&fact, url)),
+    ))
+}
+
+#[debug_handler]
+pub async fn get_fact_card(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    audience: Audience,
 ) -> Result<impl IntoResponse, AppError> {
     let id = FactId::new(id)?;
-    let result: HttpFactResponse = state.facts.get(id).await?.into();
+    let (fact, timing) = timed(state.facts.get(id, audience == Audience::Anonymous)).await;
+
+    Ok((
+        StatusCode::OK,
+        timing,
+        [(CONTENT_TYPE, SVG_CONTENT_TYPE)],
+        render_card(&fact?),
+    ))
+}
+
+#[debug_handler]
+pub async fn get_random_fact(
+    State(state): State<AppState>,
+    Query(query): Query<HttpRandomQuery>,
+    audience: Audience,
+    format: ResponseFormat,
+    fields: Fields,
+) -> Result<impl IntoResponse, AppError> {
+    let includes = query.includes()?;
+    let (result, timing) = match (query.bias()?, audience, query.max_length()) {
+        (RandomBias::Recency, audience, max_length) => {
+            timed(state.facts.get_random_recent(
+                max_length.unwrap_or(usize::MAX),
+                audience == Audience::Anonymous,
+            ))
+            .await
+        }
+        (RandomBias::Uniform, Audience::Anonymous, max_length) => {
+            timed(
+                state
+                    .facts
+                    .get_random_public(max_length.unwrap_or(usize::MAX)),
+            )
+            .await
+        }
+        (RandomBias::Uniform, Audience::Authenticated, Some(max_length)) => {
+            timed(state.facts.get_random_short(max_length)).await
+        }
+        (RandomBias::Uniform, Audience::Authenticated, None) => {
+            timed(state.facts.get_random()).await
+        }
+    };
+    let fact = result?;
+
+    if !includes.any() {
+        return Ok((timing, format.fact(StatusCode::OK, &fact, &fields)));
+    }
+
+    let mut meta = HttpRandomFactMeta::default();
+    if includes.neighbors() {
+        meta = meta.with_neighbors(
+            state
+                .facts
+                .neighbors(fact.id(), audience == Audience::Anonymous)
+                .await?,
+        );
+    }
+    if includes.count() {
+        meta = meta.with_count(state.facts.count(audience == Audience::Anonymous).await?);
+    }
+
+    Ok((
+        timing,
+        format.fact_with_meta(StatusCode::OK, &fact, &fields, meta),
+    ))
+}
+
+fn week_seed(date: NaiveDate) -> i64 {
+    let week = date.iso_week();
+
+    i64::from(week.year()) * 100 + i64::from(week.week())
+}
+
+#[debug_handler]
+pub async fn get_fact_of_the_week(
+    State(state): State<AppState>,
+    audience: Audience,
+    format: ResponseFormat,
+    fields: Fields,
+) -> Result<impl IntoResponse, AppError> {
+    let seed = week_seed(Utc::now().date_naive());
+    let (result, timing) = timed(
+        state
+            .facts
+            .get_seeded(seed, 0, audience == Audience::Anonymous),
+    )
+    .await;
+
+    Ok((timing, format.fact(StatusCode::OK, &result?, &fields)))
+}
+
+#[debug_handler]
+pub async fn get_feed_fact(
+    State(state): State<AppState>,
+    Query(query): Query<HttpFeedQuery>,
+    audience: Audience,
+) -> Result<impl IntoResponse, AppError> {
+    let token = match query.token() {
+        Some(raw) => raw.parse()?,
+        None => FeedToken::new(rand::random()),
+    };
+    let public_only = audience == Audience::Anonymous;
+
+    let (token, fact) = match state
+        .facts
+        .get_seeded(token.seed(), token.position(), public_only)
+        .await
+    {
+        Ok(fact) => (token, fact),
+        Err(GetRandomFactError::Empty) if token.position() > 0 => {
+            let token = FeedToken::new(rand::random());
+            let fact = state
+                .facts
+                .get_seeded(token.seed(), token.position(), public_only)
+                .await?;
+            (token, fact)
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(HttpFeedResponse::new(
+            &fact,
+            token.next(),
+            state.expose_actors,
+        )),
+    ))
+}
+
+#[debug_handler]
+pub async fn list_facts(
+    State(state): State<AppState>,
+    Query(query): Query<HttpListFactsQuery>,
+    pagination: Pagination,
+    audience: Audience,
+    format: ResponseFormat,
+    fields: Fields,
+) -> Result<impl IntoResponse, AppError> {
+    let (tags, mode) = query.tag_filter()?.unwrap_or_default();
+    let request = query
+        .into_request(pagination)?
+        .with_tags(tags, mode)
+        .with_public_only(audience == Audience::Anonymous);
+    let (result, timing) = timed(state.facts.list_with_total(&request)).await;
+    let (result, total) = result?;
+
+    Ok((timing, format.page(StatusCode::OK, result, total, &fields)))
+}
+
+#[debug_handler]
+pub async fn list_changes(
+    State(state): State<AppState>,
+    Query(query): Query<HttpChangesQuery>,
+    audience: Audience,
+    format: ResponseFormat,
+    fields: Fields,
+) -> Result<impl IntoResponse, AppError> {
+    let (result, timing) = timed(
+        state
+            .facts
+            .modified_since(query.since(), audience == Audience::Anonymous),
+    )
+    .await;
+
+    Ok((timing, format.facts(StatusCode::OK, result?, &fields)))
+}
+
+#[debug_handler]
+pub async fn list_recent(
+    State(state): State<AppState>,
+    Query(query): Query<HttpRecentQuery>,
+    audience: Audience,
+    format: ResponseFormat,
+    fields: Fields,
+) -> Result<impl IntoResponse, AppError> {
+    let (result, timing) = timed(
+        state
+            .facts
+            .recent(query.minutes(), audience == Audience::Anonymous),
+    )
+    .await;
+
+    Ok((timing, format.facts(StatusCode::OK, result?, &fields)))
+}
+
+#[debug_handler]
+pub async fn get_digest(
+    State(state): State<AppState>,
+    audience: Audience,
+) -> Result<impl IntoResponse, AppError> {
+    let (digest, timing) = timed(state.facts.digest(audience == Audience::Anonymous)).await;
+
+    Ok((
+        StatusCode::OK,
+        timing,
+        Json(HttpDigestResponse::from(digest?)),
+    ))
+}
+
+#[debug_handler]
+pub async fn get_extremes(
+    State(state): State<AppState>,
+    audience: Audience,
+) -> Result<impl IntoResponse, AppError> {
+    let extremes = state
+        .facts
+        .extremes(audience == Audience::Anonymous)
+        .await?;
+
+    Ok(Json(HttpExtremesResponse::new(
+        &extremes,
+        state.expose_actors,
+    )))
+}
+
+#[debug_handler]
+pub async fn get_limits(State(state): State<AppState>) -> impl IntoResponse {
+    Json(HttpLimitsResponse::new(
+        state.min_body_length,
+        state.max_search_results,
+    ))
+}
+
+#[debug_handler]
+pub async fn find_facts(
+    State(state): State<AppState>,
+    audience: Audience,
+    format: ResponseFormat,
+    fields: Fields,
+    Json(body): Json<HttpFindFactsRequestBody>,
+) -> Result<impl IntoResponse, AppError> {
+    let limit = body.limit().map_or(state.max_search_results, |limit| {
+        limit.min(state.max_search_results)
+    });
+    let request = FindFactsRequest::try_from(body)?
+        .with_limit(limit.saturating_add(1))
+        .with_public_only(audience == Audience::Anonymous);
+    let (result, timing) = timed(state.facts.find(&request)).await;
+    let mut result = result?;
+
+    let truncated = result.len() > limit as usize;
+    result.truncate(limit as usize);
+
+    let mut response = (timing, format.facts(StatusCode::OK, result, &fields)).into_response();
+    if truncated {
+        response
+            .headers_mut()
+            .insert(RESULTS_TRUNCATED_HEADER, HeaderValue::from_static("true"));
+    }
+
+    Ok(response)
+}
+
+#[debug_handler]
+pub async fn count_by_tag(
+    State(state): State<AppState>,
+    audience: Audience,
+) -> Result<impl IntoResponse, AppError> {
+    let (counts, timing) = timed(state.facts.count_by_tag(audience == Audience::Anonymous)).await;
+    let counts = counts?;
+
+    Ok((
+        StatusCode::OK,
+        timing,
+        Json(
+            counts
+                .into_iter()
+                .map(HttpTagCountResponse::from)
+                .collect::<Vec<_>>(),
+        ),
+    ))
+}
+
+#[debug_handler]
+pub async fn count_by_day(
+    State(state): State<AppState>,
+    Query(query): Query<HttpCountByDayQuery>,
+    audience: Audience,
+) -> Result<impl IntoResponse, AppError> {
+    let (counts, timing) = timed(state.facts.count_by_day(
+        query.created_after(),
+        query.created_before(),
+        audience == Audience::Anonymous,
+    ))
+    .await;
+    let counts = counts?;
+
+    Ok((
+        StatusCode::OK,
+        timing,
+        Json(
+            counts
+                .into_iter()
+                .map(HttpDayCountResponse::from)
+                .collect::<Vec<_>>(),
+        ),
+    ))
+}
+
+#[debug_handler]
+pub async fn get_feed(
+    State(state): State<AppState>,
+    audience: Audience,
+) -> Result<impl IntoResponse, AppError> {
+    let facts = state
+        .facts
+        .latest(FEED_SIZE, audience == Audience::Anonymous)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        [(CONTENT_TYPE, RSS_CONTENT_TYPE)],
+        render_rss(&state.public_url, &facts),
+    ))
+}
+
+#[debug_handler]
+pub async fn get_tag_feed(
+    State(state): State<AppState>,
+    Path(file): Path<String>,
+    audience: Audience,
+) -> Result<impl IntoResponse, AppError> {
+    let tag = file.strip_suffix(".xml").ok_or(AppError {
+        status_code: StatusCode::NOT_FOUND,
+        details: format!("Feed '{file}' doesn't exist"),
+    })?;
+    let facts = state
+        .facts
+        .latest_tagged(
+            &FactTag::new(tag)?,
+            FEED_SIZE,
+            audience == Audience::Anonymous,
+        )
+        .await?;
 
-    Ok((StatusCode::OK, Json(result)))
+    Ok((
+        StatusCode::OK,
+        [(CONTENT_TYPE, RSS_CONTENT_TYPE)],
+        render_rss(&state.public_url, &facts),
+    ))
 }
 
 #[debug_handler]
-pub async fn get_random_fact(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
-    let result: HttpFactResponse = state.facts.get_random().await?.into();
+pub async fn stream_changes(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let changes = state
+        .changes
+        .as_ref()
+        .ok_or_else(|| service::disabled("Change stream"))?;
+
+    let stream = BroadcastStream::new(changes.subscribe()).filter_map(|change| {
+        change
+            .ok()
+            .map(|data| Ok::<_, Infallible>(Event::default().event("facts_changed").data(data)))
+    });
 
-    Ok((StatusCode::OK, Json(result)))
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+pub fn audit_write(state: &AppState, actor: &Actor, id: FactId, action: &str) {
+    if state.log_writes {
+        info!(
+            target : TRACING_AUDIT_TARGET,
+            id = i32::from(id),
+            actor = actor.name().as_deref(),
+            "Fact {action}"
+        );
+    }
 }
 
 #[debug_handler]
 pub async fn create_fact(
     State(state): State<AppState>,
+    Extension(actor): Extension<Actor>,
+    if_none_match: Option<TypedHeader<IfNoneMatch>>,
+    format: ResponseFormat,
     Json(body): Json<HttpCreateFactRequestBody>,
 ) -> Result<impl IntoResponse, AppError> {
-    let request: CreateFactRequest = body.try_into()?;
-    let result: HttpFactResponse = state.facts.create(&request).await?.into();
+    let if_absent = matches!(
+        if_none_match,
+        Some(TypedHeader(condition)) if condition == IfNoneMatch::any()
+    );
+    let result = service::create_fact(
+        &state,
+        &actor,
+        body.title(),
+        &String::from(body.body().to_owned()),
+        body.public(),
+        if_absent,
+    )
+    .await?;
+
+    Ok((
+        [(LOCATION, permalink(&state.public_url, &result))],
+        format.fact(
+            StatusCode::CREATED,
+            &result,
+            &Fields::default().with_actors(state.expose_actors),
+        ),
+    ))
+}
+
+#[debug_handler]
+pub async fn patch_fact(
+    State(state): State<AppState>,
+    Extension(actor): Extension<Actor>,
+    Path(id): Path<i32>,
+    format: ResponseFormat,
+    Json(patch): Json<HttpMergePatchFactBody>,
+) -> Result<impl IntoResponse, AppError> {
+    let id = FactId::new(id)?;
+    let current = state.facts.get(id, false).await?;
+    let patched = patch.merge_into(&current);
+    let body = service::validate_body(&state, &String::from(patched.body().to_owned()))?;
+    let result = state
+        .facts
+        .update(
+            id,
+            patched.title(),
+            &body,
+            actor.name().as_deref(),
+            state.reject_duplicate_bodies,
+        )
+        .await?;
+    audit_write(&state, &actor, id, "updated");
+
+    Ok(format.fact(
+        StatusCode::OK,
+        &result,
+        &Fields::default().with_actors(state.expose_actors),
+    ))
+}
+
+#[debug_handler]
+pub async fn like_fact(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    audience: Audience,
+) -> Result<impl IntoResponse, AppError> {
+    let id = FactId::new(id)?;
+    let likes = state
+        .facts
+        .like(id, audience == Audience::Anonymous)
+        .await?;
 
-    Ok((StatusCode::CREATED, Json(result)))
+    Ok(Json(HttpLikesResponse::from(likes)))
 }
 
 #[debug_handler]
 pub async fn delete_fact(
     State(state): State<AppState>,
+    Extension(actor): Extension<Actor>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let id = FactId::new(id)?;
+    match state.facts.delete(id).await {
+        Ok(()) => audit_write(&state, &actor, id, "deleted"),
+        Err(DeleteFactError::NoSuchFact { id: _ }) if state.idempotent_delete => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[debug_handler]
+pub async fn add_fact_tag(
+    State(state): State<AppState>,
+    Extension(actor): Extension<Actor>,
     Path(id): Path<i32>,
+    Json(body): Json<HttpAddFactTagRequestBody>,
+) -> Result<impl IntoResponse, AppError> {
+    let id = FactId::new(id)?;
+    let tag: FactTag = body.try_into()?;
+    state.facts.add_tag(id, &tag).await?;
+    audit_write(&state, &actor, id, "tagged");
+
+    Ok(StatusCode::OK)
+}
+
+#[debug_handler]
+pub async fn remove_fact_tag(
+    State(state): State<AppState>,
+    Extension(actor): Extension<Actor>,
+    Path((id, tag)): Path<(i32, String)>,
 ) -> Result<impl IntoResponse, AppError> {
     let id = FactId::new(id)?;
-    state.facts.delete(id).await?;
+    let tag = FactTag::new(&tag)?;
+    state.facts.remove_tag(id, &tag).await?;
+    audit_write(&state, &actor, id, "untagged");
 
     Ok(StatusCode::NO_CONTENT)
 }
 
+async fn is_alive(state: &AppState) -> bool {
+    if state.disable_random {
+        state.facts.count(false).await.is_ok()
+    } else {
+        state.facts.get_random().await.is_ok()
+    }
+}
+
 #[debug_handler]
 pub async fn health(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
-    if state.facts.get_random().await.is_ok() {
+    if is_alive(&state).await {
         Ok((StatusCode::OK, Json("Healthy")))
     } else {
         Ok((StatusCode::SERVICE_UNAVAILABLE, Json("Unhealthy")))
     }
 }
 
-pub async fn auth_middleware(
-    State(state): State<AppState>,
-    TypedHeader(auth): TypedHeader<Authorization<Basic>>,
-    request: Request,
-    next: Next,
-) -> Result<impl IntoResponse, AppError> {
+#[debug_handler]
+pub async fn detailed_health(State(state): State<AppState>) -> impl IntoResponse {
+    let (status_code, status) = if is_alive(&state).await {
+        (StatusCode::OK, "Healthy")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "Unhealthy")
+    };
+
+    (
+        status_code,
+        Json(HttpDetailedHealthResponse::new(
+            status,
+            state.facts.pool_stats(),
+        )),
+    )
+}
+
+#[debug_handler]
+pub async fn health_probe(State(state): State<AppState>) -> StatusCode {
+    if is_alive(&state).await {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+#[debug_handler]
+pub async fn readiness(State(state): State<AppState>) -> impl IntoResponse {
+    if state.draining.load(Ordering::Relaxed) {
+        (StatusCode::SERVICE_UNAVAILABLE, Json("Draining"))
+    } else {
+        (StatusCode::OK, Json("Ready"))
+    }
+}
+
+pub fn verify_credentials(state: &AppState, auth: &Authorization<Basic>) -> Result<(), AppError> {
     let hashed = PasswordHash::new(&state.auth_key).map_err(|err| AppError {
         status_code: StatusCode::INTERNAL_SERVER_ERROR,
         details: format!("Auth failed: Can't hash the stored API key: {err}").to_owned(),
@@ -97,51 +687,209 @@ pub async fn auth_middleware(
         .map_err(|_| AppError {
             status_code: StatusCode::FORBIDDEN,
             details: "Auth failed: Hashes mismatch".to_owned(),
-        })?;
+        })
+}
+
+impl FromRequestParts<AppState> for Audience {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let authenticated = TypedHeader::<Authorization<Basic>>::from_request_parts(parts, state)
+            .await
+            .is_ok_and(|TypedHeader(auth)| verify_credentials(state, &auth).is_ok());
+
+        Ok(if authenticated {
+            Self::Authenticated
+        } else {
+            Self::Anonymous
+        })
+    }
+}
+
+pub async fn auth_middleware(
+    State(state): State<AppState>,
+    auth: Option<TypedHeader<Authorization<Basic>>>,
+    mut request: Request,
+    next: Next,
+) -> Result<impl IntoResponse, AppError> {
+    let Some(TypedHeader(auth)) = auth else {
+        return Err(AppError {
+            status_code: StatusCode::UNAUTHORIZED,
+            details: "Auth failed: Missing credentials".to_owned(),
+        });
+    };
+    verify_credentials(&state, &auth)?;
+
+    request.extensions_mut().insert(Actor::new(auth.username()));
+
+    Ok(next.run(request).await)
+}
+
+fn require_content_type(request: &Request, expected: &str) -> Result<(), AppError> {
+    let matches = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case(expected));
+
+    if !matches {
+        return Err(AppError {
+            status_code: StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            details: format!("Expected request with `Content-Type: {expected}`"),
+        });
+    }
+
+    Ok(())
+}
+
+pub async fn json_content_type_middleware(
+    request: Request,
+    next: Next,
+) -> Result<impl IntoResponse, AppError> {
+    require_content_type(&request, JSON_CONTENT_TYPE)?;
+
+    Ok(next.run(request).await)
+}
+
+pub async fn merge_patch_content_type_middleware(
+    request: Request,
+    next: Next,
+) -> Result<impl IntoResponse, AppError> {
+    require_content_type(&request, MERGE_PATCH_CONTENT_TYPE)?;
 
     Ok(next.run(request).await)
 }
 
 impl From<AppRouter> for Router<AppState> {
     fn from(app_router: AppRouter) -> Self {
-        Router::new()
-            .route(
-                "/",
-                post(create_fact).route_layer(from_fn_with_state(
+        let server_timing = app_router.state.server_timing;
+        let endpoints = app_router.state.endpoints;
+        let random = if app_router.state.disable_random {
+            get(|| async { StatusCode::NOT_FOUND })
+        } else {
+            get(get_random_fact)
+        };
+        let create = if endpoints.create {
+            post(create_fact)
+                .route_layer(from_fn(json_content_type_middleware))
+                .route_layer(from_fn_with_state(
                     app_router.state.clone(),
                     auth_middleware,
-                )),
-            )
+                ))
+        } else {
+            post(|| async { StatusCode::NOT_FOUND })
+        };
+        let remove = if endpoints.delete {
+            delete(delete_fact).route_layer(from_fn_with_state(
+                app_router.state.clone(),
+                auth_middleware,
+            ))
+        } else {
+            delete(|| async { StatusCode::NOT_FOUND })
+        };
+        let find = if endpoints.search {
+            post(find_facts)
+        } else {
+            post(|| async { StatusCode::NOT_FOUND })
+        };
+        let router = Router::new()
+            .route("/", get(list_facts))
+            .route("/", create)
             .route("/{id}", get(get_fact))
+            .route("/{id}", remove)
             .route(
                 "/{id}",
-                delete(delete_fact)
+                patch(patch_fact)
+                    .route_layer(from_fn(merge_patch_content_type_middleware))
+                    .route_layer(from_fn_with_state(
+                        app_router.state.clone(),
+                        auth_middleware,
+                    )),
+            )
+            .route("/{id}/json-ld", get(get_fact_json_ld))
+            .route("/{id}/card.svg", get(get_fact_card))
+            .route("/{id}/like", post(like_fact))
+            .route(
+                "/{id}/tags",
+                post(add_fact_tag)
+                    .route_layer(from_fn(json_content_type_middleware))
+                    .route_layer(from_fn_with_state(
+                        app_router.state.clone(),
+                        auth_middleware,
+                    )),
+            )
+            .route(
+                "/{id}/tags/{tag}",
+                delete(remove_fact_tag)
                     .route_layer(from_fn_with_state(app_router.state, auth_middleware)),
             )
-            .route("/random", get(get_random_fact))
-            .route("/health", get(health))
+            .route("/find", find)
+            .route("/tags", get(count_by_tag))
+            .route("/stats/by-day", get(count_by_day))
+            .route("/changes", get(list_changes))
+            .route("/recent", get(list_recent))
+            .route("/digest", get(get_digest))
+            .route("/limits", get(get_limits))
+            .route("/extremes", get(get_extremes))
+            .route("/random", random)
+            .route("/this-week", get(get_fact_of_the_week))
+            .route("/feed", get(get_feed_fact))
+            .route("/feed.xml", get(get_feed))
+            .route("/feed/{file}", get(get_tag_feed))
+            .route("/events", get(stream_changes))
+            .route("/health", get(health).head(health_probe))
+            .route("/health/detailed", get(detailed_health))
+            .route("/readyz", get(readiness));
+
+        if server_timing {
+            router.layer(from_fn(server_timing_middleware))
+        } else {
+            router
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
 
     use axum::{body::Body, http::Request};
+    use chrono::{DateTime, Utc};
     use fake::{Fake, Faker};
     use http_body_util::BodyExt;
     use reqwest::{
-        header::{AUTHORIZATION, CONTENT_TYPE},
+        header::{AUTHORIZATION, IF_NONE_MATCH, RETRY_AFTER},
         Method,
     };
     use serde_json::from_slice;
-    use sqlx::{query, query_scalar, PgPool};
+    use sqlx::{
+        postgres::{PgConnectOptions, PgPoolOptions},
+        query,
+        query_scalar,
+        PgPool,
+    };
     use tower::ServiceExt;
+    use tracing::subscriber::set_default;
 
     use super::*;
-    use crate::facts::{
-        repository::{Fact, FactBody, FactTitle},
-        SqlxFactsRepository,
+    use crate::{
+        facts::{
+            repository::{ControlCharacters, Fact, FactBody, FactTitle},
+            router::{
+                format::TOTAL_COUNT_HEADER,
+                models::{HttpDigestResponse, HttpFactResponse},
+                AutoTagRules,
+                Endpoints,
+                ProfanityFilter,
+                ProfanityMode,
+            },
+            SqlxFactsRepository,
+        },
+        test_support::CapturedLogs,
     };
 
     #[sqlx::test(
@@ -188,21 +936,16 @@ mod tests {
             FactId::new(response.id()).unwrap(),
             &FactTitle::new(response.title()).unwrap(),
             &FactBody::new(response.body()).unwrap(),
+            response.created_at(),
         );
 
         assert_eq!(entity.body(), result.body());
         assert_eq!(entity.title(), result.title());
     }
 
-    #[sqlx::test(
-        migrations = "./src/facts/migrations",
-        fixtures(path = "fixtures", scripts("truncate_facts_table"))
-    )]
-    async fn get_non_existent(pool: PgPool) {
-        let state = AppState {
-            facts: Arc::new(SqlxFactsRepository::new(pool)),
-            ..Default::default()
-        };
+    #[tokio::test]
+    async fn get_json_ld() {
+        let state = AppState::default();
         let router: Router<AppState> = AppRouter::new(state.clone()).into();
 
         let raw_response = router
@@ -210,22 +953,165 @@ mod tests {
             .oneshot(
                 Request::builder()
                     .method(Method::GET)
-                    .uri("/1")
+                    .uri("/7/json-ld")
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(raw_response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(raw_response.status(), StatusCode::OK);
+        assert_eq!(raw_response.headers()[CONTENT_TYPE], JSON_LD_CONTENT_TYPE);
+
+        let response = from_slice::<serde_json::Value>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(response["@context"], "https://schema.org");
+        assert_eq!(response["@type"], "Article");
+        assert_eq!(response["@id"], "http://localhost:8080/api/facts/7");
+        assert_eq!(response["headline"], "About smoking");
+        assert_eq!(response["datePublished"], "1970-01-01T00:00:00Z");
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn get_random(pool: PgPool) {
-        for _ in 0..10 {
+    async fn get_card(pool: PgPool) {
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            "Tom & <Jerry>",
+            "Cats \"chase\" mice, and mice chase cheese in every cartoon ever drawn since the very first one",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(format!("/{id}/card.svg"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+        assert_eq!(raw_response.headers()[CONTENT_TYPE], SVG_CONTENT_TYPE);
+
+        let raw_card = String::from_utf8(
+            raw_response
+                .into_body()
+                .collect()
+                .await
+                .unwrap()
+                .to_bytes()
+                .to_vec(),
+        )
+        .unwrap();
+
+        assert!(raw_card.contains("Tom &amp; &lt;Jerry&gt;"), "{raw_card}");
+
+        let card = roxmltree::Document::parse(&raw_card).unwrap();
+        let root = card.root_element();
+        let lines = root
+            .children()
+            .filter(|node| node.has_tag_name("text"))
+            .filter_map(|node| node.text())
+            .collect::<Vec<_>>();
+
+        assert_eq!(root.tag_name().name(), "svg");
+        assert_eq!(root.attribute("width"), Some("1200"));
+        assert_eq!(root.attribute("height"), Some("630"));
+        assert_eq!(lines[0], "Tom & <Jerry>");
+        assert_eq!(
+            lines[1..lines.len() - 1].join(" "),
+            "Cats \"chase\" mice, and mice chase cheese in every cartoon ever drawn since the very first one"
+        );
+        assert_eq!(lines.last(), Some(&format!("Fact #{id}").as_str()));
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_non_existent(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_from_saturated_pool(
+        pool_options: PgPoolOptions,
+        connect_options: PgConnectOptions,
+    ) {
+        let pool = pool_options
+            .max_connections(1)
+            .acquire_timeout(Duration::from_millis(100))
+            .connect_with(connect_options)
+            .await
+            .unwrap();
+        let _connection = pool.acquire().await.unwrap();
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool.clone())),
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(raw_response.headers().contains_key(RETRY_AFTER));
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_random(pool: PgPool) {
+        for _ in 0..10 {
             let entity = Faker.fake::<Fact>();
 
             query!(
@@ -291,47 +1177,100 @@ mod tests {
         assert_eq!(raw_response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[test]
+    fn week_seed_is_stable_within_iso_week() {
+        let monday = NaiveDate::from_ymd_opt(2024, 12, 30).unwrap();
+        let seed = week_seed(monday);
+
+        for day in 1..7 {
+            assert_eq!(week_seed(monday + chrono::Days::new(day)), seed);
+        }
+
+        assert_eq!(seed, 202_501);
+        assert_ne!(week_seed(monday + chrono::Days::new(7)), seed);
+    }
+
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn create_ok(pool: PgPool) {
+    async fn get_fact_of_the_week(pool: PgPool) {
+        for _ in 0..8 {
+            let entity = Faker.fake::<Fact>();
+
+            query!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2)",
+                Into::<String>::into(entity.title().to_owned()),
+                Into::<String>::into(entity.body().to_owned())
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
         let state = AppState {
             facts: Arc::new(SqlxFactsRepository::new(pool)),
             ..Default::default()
         };
+        let mut ids = Vec::new();
+
+        for _ in 0..2 {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri("/this-week")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
 
+            let response = from_slice::<HttpFactResponse>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+            ids.push(response.id());
+        }
+
+        assert_eq!(ids[0], ids[1]);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn get_fact_of_the_week_from_empty(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
         let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
         let raw_response = router
             .with_state(state)
             .oneshot(
                 Request::builder()
-                    .method(Method::POST)
-                    .uri("/")
-                    .header(CONTENT_TYPE.as_str(), "application/json")
-                    .header(AUTHORIZATION, "Basic Og==")
-                    .body(Body::from(r#"{"title": "foo", "body": "bar"}"#))
+                    .method(Method::GET)
+                    .uri("/this-week")
+                    .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(raw_response.status(), StatusCode::CREATED);
-
-        let response = from_slice::<HttpFactResponse>(
-            &raw_response.into_body().collect().await.unwrap().to_bytes(),
-        )
-        .unwrap();
-
-        assert_eq!(response.body(), "bar");
-        assert_eq!(response.title(), "foo");
+        assert_eq!(raw_response.status(), StatusCode::NOT_FOUND);
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn delete_non_existent(pool: PgPool) {
+    async fn create_ok(pool: PgPool) {
         let state = AppState {
             facts: Arc::new(SqlxFactsRepository::new(pool)),
             ..Default::default()
@@ -339,32 +1278,47 @@ mod tests {
 
         let router: Router<AppState> = AppRouter::new(state.clone()).into();
         let raw_response = router
-            .with_state(state)
+            .with_state(state.clone())
             .oneshot(
                 Request::builder()
-                    .method(Method::DELETE)
-                    .uri("/1")
+                    .method(Method::POST)
+                    .uri("/")
+                    .header(CONTENT_TYPE.as_str(), "application/json")
                     .header(AUTHORIZATION, "Basic Og==")
-                    .body(Body::empty())
+                    .body(Body::from(r#"{"title": "foo", "body": "bar"}"#))
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(raw_response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(raw_response.status(), StatusCode::CREATED);
+
+        let location = raw_response.headers()[LOCATION]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        let response = from_slice::<HttpFactResponse>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(response.body(), "bar");
+        assert_eq!(response.title(), "foo");
+        assert_eq!(
+            location,
+            format!("{}/api/facts/{}", state.public_url, response.id())
+        );
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn delete_ok(pool: PgPool) {
-        let entity = Faker.fake::<Fact>();
-
+    async fn merge_patch_title(pool: PgPool) {
         let id = query_scalar!(
             "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
-            Into::<String>::into(entity.title().to_owned()),
-            Into::<String>::into(entity.body().to_owned())
+            "foo",
+            "bar",
         )
         .fetch_one(&pool)
         .await
@@ -377,55 +1331,2877 @@ mod tests {
 
         let router: Router<AppState> = AppRouter::new(state.clone()).into();
         let raw_response = router
-            .with_state(state)
+            .with_state(state.clone())
             .oneshot(
                 Request::builder()
-                    .method(Method::DELETE)
+                    .method(Method::PATCH)
                     .uri(format!("/{id}"))
+                    .header(CONTENT_TYPE.as_str(), "application/merge-patch+json")
                     .header(AUTHORIZATION, "Basic Og==")
-                    .body(Body::empty())
+                    .body(Body::from(r#"{"title": "baz", "body": null}"#))
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(raw_response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        let response = from_slice::<HttpFactResponse>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(response.id(), id);
+        assert_eq!(response.title(), "baz");
+        assert_eq!(response.body(), "bar");
+
+        let stored = state
+            .facts
+            .get(FactId::new(id).unwrap(), false)
+            .await
+            .unwrap();
+
+        assert_eq!(String::from(stored.title().to_owned()), "baz");
+        assert_eq!(String::from(stored.body().to_owned()), "bar");
+    }
+
+    #[tokio::test]
+    async fn merge_patch_validation() {
+        let state = AppState::default();
+
+        for (uri, content_type, body, expected) in [
+            (
+                "/42",
+                "application/json",
+                r#"{"title": "baz"}"#,
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ),
+            (
+                "/42",
+                "application/merge-patch+json",
+                r#"{"title": ""}"#,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                "/45",
+                "application/merge-patch+json",
+                r#"{"title": "baz"}"#,
+                StatusCode::NOT_FOUND,
+            ),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::PATCH)
+                        .uri(uri)
+                        .header(CONTENT_TYPE.as_str(), content_type)
+                        .header(AUTHORIZATION, "Basic Og==")
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), expected, "{body}");
+        }
     }
 
     #[sqlx::test(
         migrations = "./src/facts/migrations",
         fixtures(path = "fixtures", scripts("truncate_facts_table"))
     )]
-    async fn healthcheck(pool: PgPool) {
-        let entity = Faker.fake::<Fact>();
+    async fn create_duplicate_body(pool: PgPool) {
+        for (reject_duplicate_bodies, expected) in
+            [(false, StatusCode::CREATED), (true, StatusCode::CONFLICT)]
+        {
+            let state = AppState {
+                facts: Arc::new(SqlxFactsRepository::new(pool.clone())),
+                reject_duplicate_bodies,
+                ..Default::default()
+            };
 
-        query!(
-            "INSERT INTO facts (title, body) VALUES ($1, $2)",
-            Into::<String>::into(entity.title().to_owned()),
-            Into::<String>::into(entity.body().to_owned())
-        )
-        .execute(&pool)
-        .await
-        .unwrap();
+            let mut statuses = Vec::new();
+            for title in ["foo", "baz"] {
+                let router: Router<AppState> = AppRouter::new(state.clone()).into();
+                let raw_response = router
+                    .with_state(state.clone())
+                    .oneshot(
+                        Request::builder()
+                            .method(Method::POST)
+                            .uri("/")
+                            .header(CONTENT_TYPE.as_str(), "application/json")
+                            .header(AUTHORIZATION, "Basic Og==")
+                            .body(Body::from(format!(
+                                r#"{{"title": "{title}", "body": "{reject_duplicate_bodies}"}}"#
+                            )))
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+
+                statuses.push(raw_response.status());
+            }
+
+            assert_eq!(
+                statuses,
+                [StatusCode::CREATED, expected],
+                "{reject_duplicate_bodies}"
+            );
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn patch_duplicate_body(pool: PgPool) {
+        for (reject_duplicate_bodies, expected) in
+            [(false, StatusCode::OK), (true, StatusCode::CONFLICT)]
+        {
+            let taken = format!("taken {reject_duplicate_bodies}");
+            let mut ids = Vec::new();
+            for body in [taken.clone(), format!("free {reject_duplicate_bodies}")] {
+                ids.push(
+                    query_scalar!(
+                        "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                        "foo",
+                        body,
+                    )
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap(),
+                );
+            }
+
+            let state = AppState {
+                facts: Arc::new(SqlxFactsRepository::new(pool.clone())),
+                reject_duplicate_bodies,
+                ..Default::default()
+            };
+
+            let mut statuses = Vec::new();
+            // Re-sending a fact's own body is not a duplicate.
+            for id in &ids {
+                let router: Router<AppState> = AppRouter::new(state.clone()).into();
+                let raw_response = router
+                    .with_state(state.clone())
+                    .oneshot(
+                        Request::builder()
+                            .method(Method::PATCH)
+                            .uri(format!("/{id}"))
+                            .header(CONTENT_TYPE.as_str(), "application/merge-patch+json")
+                            .header(AUTHORIZATION, "Basic Og==")
+                            .body(Body::from(format!(r#"{{"body": "{taken}"}}"#)))
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+
+                statuses.push(raw_response.status());
+            }
+
+            assert_eq!(
+                statuses,
+                [StatusCode::OK, expected],
+                "{reject_duplicate_bodies}"
+            );
+        }
+    }
 
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn create_beyond_max_total(pool: PgPool) {
         let state = AppState {
-            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            facts: Arc::new(SqlxFactsRepository::new(pool.clone())),
+            max_total_facts: Some(2),
             ..Default::default()
         };
-        let router: Router<AppState> = AppRouter::new(state.clone()).into();
 
+        let mut statuses = Vec::new();
+        for title in ["foo", "bar", "baz"] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri("/")
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .header(AUTHORIZATION, "Basic Og==")
+                        .body(Body::from(format!(
+                            r#"{{"title": "{title}", "body": "{title}"}}"#
+                        )))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            statuses.push(raw_response.status());
+        }
+
+        assert_eq!(
+            statuses,
+            [
+                StatusCode::CREATED,
+                StatusCode::CREATED,
+                StatusCode::INSUFFICIENT_STORAGE
+            ]
+        );
+        assert_eq!(state.facts.count(false).await.unwrap(), 2);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn create_auto_tagged(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool.clone())),
+            auto_tags: Some(Arc::new(
+                AutoTagRules::new(
+                    [("smoking", "health"), ("volcano", "geology")]
+                        .into_iter()
+                        .map(|(keyword, tag)| (keyword.to_owned(), tag.to_owned()))
+                        .collect(),
+                )
+                .unwrap(),
+            )),
+            ..Default::default()
+        };
+
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
         let raw_response = router
             .with_state(state)
             .oneshot(
                 Request::builder()
-                    .method(Method::GET)
-                    .uri("/health")
-                    .body(Body::empty())
+                    .method(Method::POST)
+                    .uri("/")
+                    .header(CONTENT_TYPE.as_str(), "application/json")
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::from(
+                        r#"{"title": "About smoking", "body": "Smoking kills"}"#,
+                    ))
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(raw_response.status(), StatusCode::OK);
+        assert_eq!(raw_response.status(), StatusCode::CREATED);
+
+        let response = from_slice::<HttpFactResponse>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+        let tags = query_scalar!(
+            "SELECT tag FROM fact_tags WHERE fact_id = $1 ORDER BY tag",
+            response.id()
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(tags, vec!["health".to_owned()]);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn create_if_none_match(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        for (body, status) in [
+            (r#"{"title": "foo", "body": "bar"}"#, StatusCode::CREATED),
+            (
+                r#"{"title": "foo", "body": "baz"}"#,
+                StatusCode::PRECONDITION_FAILED,
+            ),
+            (r#"{"title": "qux", "body": "baz"}"#, StatusCode::CREATED),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri("/")
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .header(AUTHORIZATION, "Basic Og==")
+                        .header(IF_NONE_MATCH, "*")
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), status);
+        }
+    }
+
+    #[tokio::test]
+    async fn create_with_wrong_content_type() {
+        for content_type in [None, Some("application/x-www-form-urlencoded")] {
+            let state = AppState::default();
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+            let mut request = Request::builder()
+                .method(Method::POST)
+                .uri("/")
+                .header(AUTHORIZATION, "Basic Og==");
+            if let Some(content_type) = content_type {
+                request = request.header(CONTENT_TYPE.as_str(), content_type);
+            }
+
+            let raw_response = router
+                .with_state(state)
+                .oneshot(
+                    request
+                        .body(Body::from(r#"{"title": "foo", "body": "bar"}"#))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        }
+    }
+
+    #[tokio::test]
+    async fn create_with_charset_content_type() {
+        let state = AppState::default();
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header(CONTENT_TYPE.as_str(), "application/json; charset=utf-8")
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::from(r#"{"title": "foo", "body": "bar"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn disabled_endpoints() {
+        let state = AppState {
+            endpoints: Endpoints {
+                create: false,
+                delete: true,
+                search: false,
+            },
+            ..Default::default()
+        };
+
+        for (method, uri, expected) in [
+            (Method::POST, "/", StatusCode::NOT_FOUND),
+            (Method::POST, "/find", StatusCode::NOT_FOUND),
+            (Method::DELETE, "/42", StatusCode::NO_CONTENT),
+            (Method::GET, "/", StatusCode::OK),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(method.clone())
+                        .uri(uri)
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .header(AUTHORIZATION, "Basic Og==")
+                        .body(Body::from(r#"{"title": "foo", "body": "bar"}"#))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), expected, "{method} {uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn create_with_control_characters() {
+        for (control_characters, expected) in [
+            (ControlCharacters::Reject, StatusCode::UNPROCESSABLE_ENTITY),
+            (ControlCharacters::Strip, StatusCode::CREATED),
+        ] {
+            let state = AppState {
+                control_characters,
+                ..Default::default()
+            };
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state)
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri("/")
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .header(AUTHORIZATION, "Basic Og==")
+                        .body(Body::from(r#"{"title": "foo", "body": "b\u0000ar"}"#))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), expected, "{control_characters:?}");
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn create_with_profanity(pool: PgPool) {
+        for (mode, expected, body) in [
+            (
+                ProfanityMode::Reject,
+                StatusCode::UNPROCESSABLE_ENTITY,
+                None,
+            ),
+            (
+                ProfanityMode::Mask,
+                StatusCode::CREATED,
+                Some("Octopuses are **** clever, Darnell says"),
+            ),
+        ] {
+            let state = AppState {
+                facts: Arc::new(SqlxFactsRepository::new(pool.clone())),
+                profanity: Some(Arc::new(ProfanityFilter::new(["darn"], mode))),
+                ..Default::default()
+            };
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state)
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri("/")
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .header(AUTHORIZATION, "Basic Og==")
+                        .body(Body::from(
+                            r#"{"title": "foo", "body": "Octopuses are DARN clever, Darnell says"}"#,
+                        ))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), expected, "{mode:?}");
+
+            let stored = query_scalar!("SELECT body FROM facts")
+                .fetch_optional(&pool)
+                .await
+                .unwrap();
+
+            assert_eq!(stored.as_deref(), body, "{mode:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn limits() {
+        let state = AppState {
+            min_body_length: 12,
+            max_search_results: 7,
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/limits")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        let response = from_slice::<serde_json::Value>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            response,
+            serde_json::json!({
+                "max_title_length": 64,
+                "min_body_length": 12,
+                "max_body_length": 2048,
+                "max_tag_length": 32,
+                "max_list_limit": 50,
+                "max_search_results": 7,
+            })
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn extremes(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool.clone())),
+            ..Default::default()
+        };
+        let extremes = || async {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri("/extremes")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        };
+
+        assert_eq!(extremes().await.status(), StatusCode::NOT_FOUND);
+
+        let mut ids = Vec::new();
+        for body in ["medium body", "a", "the longest body of them all", "b"] {
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                "title",
+                body
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
+        let raw_response = extremes().await;
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        let response = from_slice::<HttpExtremesResponse>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(response.longest().id(), ids[2]);
+        assert_eq!(response.shortest().id(), ids[1]);
+    }
+
+    #[tokio::test]
+    async fn create_min_body_length() {
+        let state = AppState {
+            min_body_length: 4,
+            ..Default::default()
+        };
+
+        for (body, expected) in [
+            ("food", StatusCode::CREATED),
+            ("foo", StatusCode::UNPROCESSABLE_ENTITY),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri("/")
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .header(AUTHORIZATION, "Basic Og==")
+                        .body(Body::from(format!(
+                            r#"{{"title": "foo", "body": "{body}"}}"#
+                        )))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), expected, "{body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn create_invalid_title_rejected_while_deserializing() {
+        let state = AppState::default();
+
+        for (title, reason) in [
+            (String::new(), "Empty title is not allowed"),
+            ("a".repeat(FactTitle::MAX_LENGTH + 1), "Title is very long"),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri("/")
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .header(AUTHORIZATION, "Basic Og==")
+                        .body(Body::from(format!(
+                            r#"{{"title": "{title}", "body": "foo"}}"#
+                        )))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+            let details = String::from_utf8(
+                raw_response
+                    .into_body()
+                    .collect()
+                    .await
+                    .unwrap()
+                    .to_bytes()
+                    .to_vec(),
+            )
+            .unwrap();
+            assert!(details.contains(reason), "{details}");
+        }
+    }
+
+    async fn create_logged(log_writes: bool) -> String {
+        let logs = CapturedLogs::default();
+        let _subscriber = set_default(
+            tracing_subscriber::fmt()
+                .with_writer(logs.clone())
+                .with_ansi(false)
+                .finish(),
+        );
+        let state = AppState {
+            log_writes,
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header(CONTENT_TYPE.as_str(), "application/json")
+                    // "alice:" with the empty password
+                    .header(AUTHORIZATION, "Basic YWxpY2U6")
+                    .body(Body::from(r#"{"title": "foo", "body": "bar"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::CREATED);
+
+        logs.contents()
+    }
+
+    async fn patched_by_bob(pool: PgPool, expose_actors: bool) -> Vec<serde_json::Value> {
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body, created_by) VALUES ($1, $2, $3) RETURNING id",
+            "foo",
+            "bar",
+            "alice",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            expose_actors,
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let router = router.with_state(state);
+
+        let raw_response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::PATCH)
+                    .uri(format!("/{id}"))
+                    .header(CONTENT_TYPE.as_str(), "application/merge-patch+json")
+                    // "bob:" with the empty password
+                    .header(AUTHORIZATION, "Basic Ym9iOg==")
+                    .body(Body::from(r#"{"title": "baz"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        let patched = from_slice::<serde_json::Value>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        let mut responses = vec![patched];
+        for (uri, pointer) in [
+            (format!("/{id}"), ""),
+            ("/feed".to_owned(), "/fact"),
+            ("/extremes".to_owned(), "/longest"),
+            ("/extremes".to_owned(), "/shortest"),
+        ] {
+            let raw_response = router
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let response = from_slice::<serde_json::Value>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            responses.push(response.pointer(pointer).unwrap().clone());
+        }
+
+        responses
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn actors_exposed_when_enabled(pool: PgPool) {
+        for response in patched_by_bob(pool, true).await {
+            assert_eq!(response["created_by"], "alice", "{response}");
+            assert_eq!(response["updated_by"], "bob", "{response}");
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn actors_hidden_by_default(pool: PgPool) {
+        for response in patched_by_bob(pool, false).await {
+            assert_eq!(response["title"], "baz", "{response}");
+            assert!(response.get("created_by").is_none(), "{response}");
+            assert!(response.get("updated_by").is_none(), "{response}");
+        }
+    }
+
+    #[tokio::test]
+    async fn create_logged_when_enabled() {
+        let logs = create_logged(true).await;
+
+        assert!(logs.contains(TRACING_AUDIT_TARGET));
+        assert!(logs.contains("Fact created"));
+        assert!(logs.contains("alice"));
+    }
+
+    #[tokio::test]
+    async fn create_not_logged_by_default() {
+        let logs = create_logged(false).await;
+
+        assert!(!logs.contains(TRACING_AUDIT_TARGET));
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn delete_non_existent(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri("/1")
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn like(pool: PgPool) {
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            "foo",
+            "bar",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let private = query_scalar!(
+            "INSERT INTO facts (title, body, public) VALUES ($1, $2, $3) RETURNING id",
+            "baz",
+            "qux",
+            false,
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool.clone())),
+            ..Default::default()
+        };
+        let like = |id: i32| {
+            let state = state.clone();
+            async move {
+                let router: Router<AppState> = AppRouter::new(state.clone()).into();
+                router
+                    .with_state(state)
+                    .oneshot(
+                        Request::builder()
+                            .method(Method::POST)
+                            .uri(format!("/{id}/like"))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap()
+            }
+        };
+
+        for expected in 1..=3 {
+            let raw_response = like(id).await;
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
+
+            let response = from_slice::<HttpLikesResponse>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(response.likes(), expected);
+        }
+
+        assert_eq!(like(private + 1).await.status(), StatusCode::NOT_FOUND);
+        assert_eq!(like(private).await.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            query_scalar!("SELECT likes FROM facts WHERE id = $1", private)
+                .fetch_one(&pool)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn delete_non_existent_idempotent(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            idempotent_delete: true,
+            ..Default::default()
+        };
+
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri("/1")
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn delete_ok(pool: PgPool) {
+        let entity = Faker.fake::<Fact>();
+
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            Into::<String>::into(entity.title().to_owned()),
+            Into::<String>::into(entity.body().to_owned())
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri(format!("/{id}"))
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn healthcheck(pool: PgPool) {
+        let entity = Faker.fake::<Fact>();
+
+        query!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2)",
+            Into::<String>::into(entity.title().to_owned()),
+            Into::<String>::into(entity.body().to_owned())
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/health")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn healthcheck_detailed(pool: PgPool) {
+        let entity = Faker.fake::<Fact>();
+
+        query!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2)",
+            Into::<String>::into(entity.title().to_owned()),
+            Into::<String>::into(entity.body().to_owned())
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/health/detailed")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        let response = from_slice::<serde_json::Value>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+        let pool = &response["pool"];
+
+        assert_eq!(response["status"], "Healthy");
+        assert!(pool["size"].as_u64().unwrap() >= 1);
+        assert_eq!(
+            pool["size"].as_u64().unwrap(),
+            pool["idle"].as_u64().unwrap() + pool["in_use"].as_u64().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn healthcheck_detailed_without_pool() {
+        let state = AppState::default();
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/health/detailed")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        let response = from_slice::<serde_json::Value>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(response["status"], "Healthy");
+        assert!(response.get("pool").is_none());
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn healthcheck_head(pool: PgPool) {
+        let entity = Faker.fake::<Fact>();
+
+        query!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2)",
+            Into::<String>::into(entity.title().to_owned()),
+            Into::<String>::into(entity.body().to_owned())
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::HEAD)
+                    .uri("/health")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+        assert!(raw_response
+            .into_body()
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes()
+            .is_empty());
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn healthcheck_head_unhealthy(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::HEAD)
+                    .uri("/health")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(raw_response
+            .into_body()
+            .collect()
+            .await
+            .unwrap()
+            .to_bytes()
+            .is_empty());
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn healthcheck_random_disabled(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            disable_random: true,
+            ..Default::default()
+        };
+
+        for method in [Method::GET, Method::HEAD] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(method.clone())
+                        .uri("/health")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK, "{method}");
+        }
+    }
+
+    #[tokio::test]
+    async fn random_disabled() {
+        for (disable_random, expected) in [(true, StatusCode::NOT_FOUND), (false, StatusCode::OK)] {
+            let state = AppState {
+                disable_random,
+                ..Default::default()
+            };
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state)
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri("/random")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), expected, "{disable_random}");
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn add_tag_ok(pool: PgPool) {
+        let entity = Faker.fake::<Fact>();
+
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            Into::<String>::into(entity.title().to_owned()),
+            Into::<String>::into(entity.body().to_owned())
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool.clone())),
+            ..Default::default()
+        };
+
+        for _ in 0..2 {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri(format!("/{id}/tags"))
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .header(AUTHORIZATION, "Basic Og==")
+                        .body(Body::from(r#"{"tag": "health"}"#))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
+        }
+
+        let tags = query_scalar!("SELECT tag FROM fact_tags WHERE fact_id = $1", id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(tags, vec!["health".to_owned()]);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn digest(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let router = router.with_state(state);
+        let digest = || async {
+            let raw_response = router
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri("/digest")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
+
+            from_slice::<HttpDigestResponse>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap()
+        };
+
+        let before = digest().await;
+        assert_eq!(before.digest().len(), 64);
+        assert_eq!(digest().await, before);
+
+        let raw_response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header(CONTENT_TYPE.as_str(), "application/json")
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::from(r#"{"title": "foo", "body": "bar"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::CREATED);
+
+        let after = digest().await;
+        assert_ne!(after, before);
+        assert_eq!(digest().await, after);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn tag_counts(pool: PgPool) {
+        for (tags, public) in [
+            (vec!["health", "science"], true),
+            (vec!["science"], true),
+            (vec!["science"], true),
+            (vec!["science", "secret"], false),
+        ] {
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body, public) VALUES ($1, $2, $3) RETURNING id",
+                "foo",
+                "bar",
+                public,
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            for tag in tags {
+                query!(
+                    "INSERT INTO fact_tags (fact_id, tag) VALUES ($1, $2)",
+                    id,
+                    tag
+                )
+                .execute(&pool)
+                .await
+                .unwrap();
+            }
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        for (authorization, expected) in [
+            (
+                None,
+                serde_json::json!([
+                    {"tag": "science", "count": 3},
+                    {"tag": "health", "count": 1},
+                ]),
+            ),
+            (
+                Some("Basic Og=="),
+                serde_json::json!([
+                    {"tag": "science", "count": 4},
+                    {"tag": "health", "count": 1},
+                    {"tag": "secret", "count": 1},
+                ]),
+            ),
+        ] {
+            let mut request = Request::builder().method(Method::GET).uri("/tags");
+            if let Some(authorization) = authorization {
+                request = request.header(AUTHORIZATION, authorization);
+            }
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
+
+            let response = from_slice::<serde_json::Value>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(response, expected, "{authorization:?}");
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn day_counts(pool: PgPool) {
+        for created_at in [
+            "2024-03-01T08:00:00Z",
+            "2024-03-01T23:59:59Z",
+            "2024-03-02T00:00:00Z",
+        ] {
+            query!(
+                "INSERT INTO facts (title, body, created_at) VALUES ($1, $2, $3)",
+                "foo",
+                "bar",
+                created_at.parse::<DateTime<Utc>>().unwrap(),
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        for (uri, expected) in [
+            (
+                "/stats/by-day",
+                serde_json::json!([
+                    {"date": "2024-03-01", "count": 2},
+                    {"date": "2024-03-02", "count": 1},
+                ]),
+            ),
+            (
+                "/stats/by-day?created_after=2024-03-01T12:00:00Z",
+                serde_json::json!([
+                    {"date": "2024-03-01", "count": 1},
+                    {"date": "2024-03-02", "count": 1},
+                ]),
+            ),
+            (
+                "/stats/by-day?created_before=2024-03-01T12:00:00Z",
+                serde_json::json!([{"date": "2024-03-01", "count": 1}]),
+            ),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK, "{uri}");
+
+            let response = from_slice::<serde_json::Value>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(response, expected, "{uri}");
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn add_tag_non_existent(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/1/tags")
+                    .header(CONTENT_TYPE.as_str(), "application/json")
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::from(r#"{"tag": "health"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn remove_tag(pool: PgPool) {
+        let entity = Faker.fake::<Fact>();
+
+        let id = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            Into::<String>::into(entity.title().to_owned()),
+            Into::<String>::into(entity.body().to_owned())
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        query!(
+            "INSERT INTO fact_tags (fact_id, tag) VALUES ($1, $2)",
+            id,
+            "health"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        for expected in [StatusCode::NO_CONTENT, StatusCode::NOT_FOUND] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::DELETE)
+                        .uri(format!("/{id}/tags/health"))
+                        .header(AUTHORIZATION, "Basic Og==")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), expected);
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list_created_window(pool: PgPool) {
+        let mut ids = Vec::new();
+
+        for created_at in [
+            "2024-01-01T00:00:00Z",
+            "2024-01-02T00:00:00Z",
+            "2024-01-03T00:00:00Z",
+            "2024-01-04T00:00:00Z",
+        ] {
+            let entity = Faker.fake::<Fact>();
+
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body, created_at) VALUES ($1, $2, $3::timestamptz) RETURNING id",
+                Into::<String>::into(entity.title().to_owned()),
+                Into::<String>::into(entity.body().to_owned()),
+                created_at.parse::<DateTime<Utc>>().unwrap(),
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/?created_after=2024-01-01T12:00:00Z&created_before=2024-01-03T12:00:00Z")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        let response = from_slice::<Vec<HttpFactResponse>>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            response
+                .iter()
+                .map(HttpFactResponse::id)
+                .collect::<Vec<_>>(),
+            ids[1..3]
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn anonymous_sees_public_facts_only(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let mut ids = Vec::new();
+        for body in [
+            r#"{"title": "foo", "body": "bar"}"#,
+            r#"{"title": "baz", "body": "qux", "public": false}"#,
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri("/")
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .header(AUTHORIZATION, "Basic Og==")
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let response = from_slice::<HttpFactResponse>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            ids.push(response.id());
+        }
+
+        for _ in 0..16 {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri("/random")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let response = from_slice::<HttpFactResponse>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(response.id(), ids[0]);
+        }
+
+        for (auth, expected) in [(None, &ids[..1]), (Some("Basic Og=="), &ids[..])] {
+            let mut request = Request::builder().method(Method::GET).uri("/");
+            if let Some(auth) = auth {
+                request = request.header(AUTHORIZATION, auth);
+            }
+
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let response = from_slice::<Vec<HttpFactResponse>>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                response
+                    .iter()
+                    .map(HttpFactResponse::id)
+                    .collect::<Vec<_>>(),
+                expected,
+                "{auth:?}"
+            );
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn anonymous_reads_skip_private_facts(pool: PgPool) {
+        let mut ids = Vec::new();
+        for (title, body, public) in [
+            ("Visible", "Short public body", true),
+            (
+                "Hidden",
+                "A much longer private body that wins every contest",
+                false,
+            ),
+        ] {
+            let id = query_scalar!(
+                r"
+INSERT INTO facts (title, body, public, updated_at)
+VALUES ($1, $2, $3, now())
+RETURNING id
+                ",
+                title,
+                body,
+                public,
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            query!(
+                "INSERT INTO fact_tags (fact_id, tag) VALUES ($1, 'shared')",
+                id
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+            ids.push(id);
+        }
+        let hidden = ids[1];
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+        let call = |request: Request<Body>| {
+            let state = state.clone();
+            async move {
+                let router: Router<AppState> = AppRouter::new(state.clone()).into();
+                let response = router.with_state(state).oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+
+                (status, String::from_utf8(body.to_vec()).unwrap())
+            }
+        };
+
+        for uri in [
+            format!("/{hidden}"),
+            format!("/{hidden}/json-ld"),
+            format!("/{hidden}/card.svg"),
+        ] {
+            let (status, _) = call(Request::get(&uri).body(Body::empty()).unwrap()).await;
+
+            assert_eq!(status, StatusCode::NOT_FOUND, "{uri}");
+
+            let (status, _) = call(
+                Request::get(&uri)
+                    .header(AUTHORIZATION, "Basic Og==")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await;
+
+            assert_eq!(status, StatusCode::OK, "{uri}");
+        }
+
+        for uri in [
+            "/this-week",
+            "/feed",
+            "/feed.xml",
+            "/feed/shared.xml",
+            "/recent?minutes=60",
+            "/changes?since=2000-01-01T00:00:00Z",
+            "/extremes",
+        ] {
+            let (status, body) = call(Request::get(uri).body(Body::empty()).unwrap()).await;
+
+            assert_eq!(status, StatusCode::OK, "{uri}");
+            assert!(body.contains("Visible"), "{uri}: {body}");
+            assert!(!body.contains("Hidden"), "{uri}: {body}");
+        }
+
+        let (status, body) = call(
+            Request::post("/find")
+                .header(CONTENT_TYPE.as_str(), "application/json")
+                .body(Body::from(r#"{"tag": "shared"}"#))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("Visible"), "{body}");
+        assert!(!body.contains("Hidden"), "{body}");
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list_by_tags(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool.clone())),
+            ..Default::default()
+        };
+
+        let mut ids = Vec::new();
+        for tags in [vec!["health", "science"], vec!["science"], vec!["history"]] {
+            let entity = Faker.fake::<Fact>();
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                Into::<String>::into(entity.title().to_owned()),
+                Into::<String>::into(entity.body().to_owned())
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            for tag in tags {
+                state
+                    .facts
+                    .add_tag(FactId::new(id).unwrap(), &FactTag::new(tag).unwrap())
+                    .await
+                    .unwrap();
+            }
+
+            ids.push(id);
+        }
+
+        for (uri, expected, total) in [
+            ("/?tags=health,science&match=all", &ids[..1], 1),
+            ("/?tags=health,science&match=any", &ids[..2], 2),
+            ("/?tags=health,science", &ids[..2], 2),
+            ("/?tags=history,science&match=all", &[][..], 0),
+            ("/?tags=science&limit=1", &ids[..1], 2),
+            ("/?tags=science&limit=1&offset=1", &ids[1..2], 2),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK, "{uri}");
+            assert_eq!(
+                raw_response.headers()[TOTAL_COUNT_HEADER],
+                total.to_string(),
+                "{uri}"
+            );
+
+            let response = from_slice::<Vec<HttpFactResponse>>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                response
+                    .iter()
+                    .map(HttpFactResponse::id)
+                    .collect::<Vec<_>>(),
+                expected,
+                "{uri}"
+            );
+        }
+
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/?tags=health&match=most")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list_total_count(pool: PgPool) {
+        for _ in 0..4 {
+            let entity = Faker.fake::<Fact>();
+
+            query!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2)",
+                Into::<String>::into(entity.title().to_owned()),
+                Into::<String>::into(entity.body().to_owned()),
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        for (offset, size) in [(0, 3), (3, 1), (6, 0)] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(format!("/?limit=3&offset={offset}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
+            assert_eq!(raw_response.headers()[TOTAL_COUNT_HEADER], "4");
+
+            let response = from_slice::<Vec<HttpFactResponse>>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(response.len(), size);
+        }
+    }
+
+    #[tokio::test]
+    async fn list_pagination_validation() {
+        let state = AppState::default();
+
+        for (uri, expected, details) in [
+            ("/?limit=3&offset=2", StatusCode::OK, None),
+            ("/?offset=0", StatusCode::OK, None),
+            (
+                "/?offset=-1",
+                StatusCode::BAD_REQUEST,
+                Some("Invalid offset -1, must not be negative"),
+            ),
+            (
+                "/?limit=ten",
+                StatusCode::BAD_REQUEST,
+                Some("Invalid limit \"ten\", expected an integer between 0 and 4294967295"),
+            ),
+            (
+                "/?limit=-5&offset=1",
+                StatusCode::BAD_REQUEST,
+                Some("Invalid limit -5, must not be negative"),
+            ),
+            ("/?limit=0", StatusCode::UNPROCESSABLE_ENTITY, None),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), expected, "{uri}");
+
+            if let Some(details) = details {
+                assert_eq!(
+                    raw_response.into_body().collect().await.unwrap().to_bytes(),
+                    details,
+                    "{uri}"
+                );
+            }
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list_changes(pool: PgPool) {
+        let mut ids = Vec::new();
+
+        for updated_at in ["2024-01-01T00:00:00Z", "2024-01-02T00:00:00Z"] {
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body, updated_at) VALUES ($1, $2, $3::timestamptz) RETURNING id",
+                "foo",
+                "bar",
+                updated_at.parse::<DateTime<Utc>>().unwrap(),
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        for (uri, expected) in [
+            ("/changes?since=2023-12-31T00:00:00Z", ids.clone()),
+            ("/changes?since=2024-01-01T12:00:00Z", vec![ids[1]]),
+            ("/changes?since=2024-01-02T00:00:00Z", vec![]),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
+
+            let response = from_slice::<Vec<HttpFactResponse>>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                response
+                    .iter()
+                    .map(HttpFactResponse::id)
+                    .collect::<Vec<_>>(),
+                expected
+            );
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list_recent(pool: PgPool) {
+        let mut ids = Vec::new();
+
+        for seconds in [3600, 60] {
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body, created_at) VALUES ($1, $2, $3) RETURNING id",
+                "foo",
+                "bar",
+                Utc::now() - chrono::TimeDelta::seconds(seconds),
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        for (uri, expected) in [
+            ("/recent?minutes=120", Some(ids.clone())),
+            ("/recent?minutes=30", Some(vec![ids[1]])),
+            ("/recent?minutes=0", Some(vec![])),
+            ("/recent", None),
+            ("/recent?minutes=-1", None),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            let Some(expected) = expected else {
+                assert_eq!(raw_response.status(), StatusCode::BAD_REQUEST, "{uri}");
+                continue;
+            };
+            assert_eq!(raw_response.status(), StatusCode::OK, "{uri}");
+
+            let response = from_slice::<Vec<HttpFactResponse>>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                response
+                    .iter()
+                    .map(HttpFactResponse::id)
+                    .collect::<Vec<_>>(),
+                expected,
+                "{uri}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn list_changes_without_since() {
+        let state = AppState::default();
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/changes")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn random_short(pool: PgPool) {
+        let short = query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            "Short",
+            "Tiny body",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        query_scalar!(
+            "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+            "Long",
+            "A considerably longer body that does not fit",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        for (uri, expected) in [
+            ("/random?max_length=20", StatusCode::OK),
+            ("/random?max_length=5", StatusCode::NOT_FOUND),
+            ("/random?max_length=short", StatusCode::BAD_REQUEST),
+        ] {
+            for _ in 0..5 {
+                let router: Router<AppState> = AppRouter::new(state.clone()).into();
+                let raw_response = router
+                    .with_state(state.clone())
+                    .oneshot(
+                        Request::builder()
+                            .method(Method::GET)
+                            .uri(uri)
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+
+                assert_eq!(raw_response.status(), expected);
+
+                if expected == StatusCode::OK {
+                    let response = from_slice::<HttpFactResponse>(
+                        &raw_response.into_body().collect().await.unwrap().to_bytes(),
+                    )
+                    .unwrap();
+
+                    assert_eq!(response.id(), short);
+                }
+            }
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn random_includes(pool: PgPool) {
+        let mut ids = Vec::new();
+        for title in ["first", "second", "third"] {
+            ids.push(
+                query_scalar!(
+                    "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                    title,
+                    "body",
+                )
+                .fetch_one(&pool)
+                .await
+                .unwrap(),
+            );
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        for _ in 0..5 {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri("/random?include=neighbors,count")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
+
+            let response = from_slice::<serde_json::Value>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+            let position = ids
+                .iter()
+                .position(|id| response["fact"]["id"] == *id)
+                .unwrap();
+
+            assert_eq!(response["count"], 3);
+            assert_eq!(
+                response["neighbors"]["previous"],
+                position
+                    .checked_sub(1)
+                    .map_or(serde_json::Value::Null, |previous| ids[previous].into())
+            );
+            assert_eq!(
+                response["neighbors"]["next"],
+                ids.get(position + 1)
+                    .map_or(serde_json::Value::Null, |next| (*next).into())
+            );
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn random_includes_anonymous_skip_private(pool: PgPool) {
+        let mut ids = Vec::new();
+        for (title, public) in [("first", true), ("second", false), ("third", true)] {
+            ids.push(
+                query_scalar!(
+                    "INSERT INTO facts (title, body, public) VALUES ($1, $2, $3) RETURNING id",
+                    title,
+                    "body",
+                    public,
+                )
+                .fetch_one(&pool)
+                .await
+                .unwrap(),
+            );
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        for _ in 0..5 {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri("/random?include=neighbors,count")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
+
+            let response = from_slice::<serde_json::Value>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(response["count"], 2);
+            assert_ne!(response["fact"]["id"], ids[1]);
+            assert_ne!(response["neighbors"]["previous"], ids[1]);
+            assert_ne!(response["neighbors"]["next"], ids[1]);
+            if response["fact"]["id"] == ids[0] {
+                assert_eq!(response["neighbors"]["next"], ids[2]);
+            } else {
+                assert_eq!(response["neighbors"]["previous"], ids[0]);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn random_include_validation() {
+        let state = AppState::default();
+
+        for (uri, expected) in [
+            ("/random?include=count", StatusCode::OK),
+            ("/random?include=neighbors", StatusCode::OK),
+            ("/random?include=count,tags", StatusCode::BAD_REQUEST),
+            ("/random?include=", StatusCode::BAD_REQUEST),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), expected, "{uri}");
+
+            if expected == StatusCode::OK {
+                let response = from_slice::<serde_json::Value>(
+                    &raw_response.into_body().collect().await.unwrap().to_bytes(),
+                )
+                .unwrap();
+                let object = response.as_object().unwrap();
+
+                assert_eq!(response["fact"]["id"], 42);
+                assert_eq!(object.contains_key("count"), uri.ends_with("count"));
+                assert_eq!(object.contains_key("neighbors"), uri.ends_with("neighbors"));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn random_bias_validation() {
+        let state = AppState::default();
+
+        for (uri, expected) in [
+            ("/random?bias=uniform", StatusCode::OK),
+            ("/random?bias=recency", StatusCode::OK),
+            ("/random?bias=recency&max_length=1", StatusCode::NOT_FOUND),
+            ("/random?bias=oldest", StatusCode::BAD_REQUEST),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), expected, "{uri}");
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list_created_by(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+        let mut ids = Vec::new();
+
+        // "alice:" and "bob:" with the empty password
+        for credentials in ["YWxpY2U6", "Ym9iOg==", "YWxpY2U6"] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri("/")
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .header(AUTHORIZATION, format!("Basic {credentials}"))
+                        .body(Body::from(r#"{"title": "foo", "body": "bar"}"#))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::CREATED);
+
+            let response = from_slice::<HttpFactResponse>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+            ids.push(response.id());
+        }
+
+        for (actor, expected) in [("alice", vec![ids[0], ids[2]]), ("bob", vec![ids[1]])] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(format!("/?created_by={actor}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
+
+            let response = from_slice::<Vec<HttpFactResponse>>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                response
+                    .iter()
+                    .map(HttpFactResponse::id)
+                    .collect::<Vec<_>>(),
+                expected
+            );
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn find_title_contains(pool: PgPool) {
+        let mut ids = Vec::new();
+
+        for title in ["About smoking", "About sleeping"] {
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                title,
+                "bar",
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/find")
+                    .header(CONTENT_TYPE.as_str(), "application/json")
+                    .body(Body::from(r#"{"title_contains": "smok"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+
+        let response = from_slice::<Vec<HttpFactResponse>>(
+            &raw_response.into_body().collect().await.unwrap().to_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            response
+                .iter()
+                .map(HttpFactResponse::id)
+                .collect::<Vec<_>>(),
+            ids[..1]
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn find_capped(pool: PgPool) {
+        for _ in 0..4 {
+            query!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2)",
+                "About smoking",
+                "bar",
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            max_search_results: 3,
+            ..Default::default()
+        };
+
+        for (body, expected, truncated) in [
+            (r#"{"title_contains": "smok"}"#, 3, true),
+            (r#"{"title_contains": "smok", "limit": 100}"#, 3, true),
+            (r#"{"title_contains": "smok", "limit": 2}"#, 2, true),
+            (r#"{"title_contains": "smok", "limit": 0}"#, 0, true),
+            (r#"{"title_contains": "sleep"}"#, 0, false),
+        ] {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri("/find")
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
+            assert_eq!(
+                raw_response
+                    .headers()
+                    .contains_key(RESULTS_TRUNCATED_HEADER),
+                truncated
+            );
+
+            let response = from_slice::<Vec<HttpFactResponse>>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+
+            assert_eq!(response.len(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn find_rejected_matchers() {
+        for body in [
+            r#"{"title_matches": "smok.*"}"#,
+            "{}",
+            r#"{"tag": "two words"}"#,
+        ] {
+            let state = AppState::default();
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+            let raw_response = router
+                .with_state(state)
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri("/find")
+                        .header(CONTENT_TYPE.as_str(), "application/json")
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        }
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn list_invalid_timestamp(pool: PgPool) {
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/?created_after=yesterday")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn feed(pool: PgPool) {
+        let mut ids = Vec::new();
+
+        for created_at in [
+            "2024-01-02T00:00:00Z",
+            "2024-01-03T00:00:00Z",
+            "2024-01-01T00:00:00Z",
+        ] {
+            let entity = Faker.fake::<Fact>();
+
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body, created_at) VALUES ($1, $2, $3::timestamptz) RETURNING id",
+                Into::<String>::into(entity.title().to_owned()),
+                Into::<String>::into(entity.body().to_owned()),
+                created_at.parse::<DateTime<Utc>>().unwrap(),
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let raw_response = router
+            .with_state(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/feed.xml")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::OK);
+        assert_eq!(raw_response.headers()[CONTENT_TYPE], "application/rss+xml");
+
+        let raw_feed = String::from_utf8(
+            raw_response
+                .into_body()
+                .collect()
+                .await
+                .unwrap()
+                .to_bytes()
+                .to_vec(),
+        )
+        .unwrap();
+        let feed = roxmltree::Document::parse(&raw_feed).unwrap();
+        let guids = feed
+            .descendants()
+            .filter(|node| node.has_tag_name("item"))
+            .map(|item| {
+                item.children()
+                    .find(|node| node.has_tag_name("guid"))
+                    .and_then(|guid| guid.text())
+                    .unwrap()
+                    .to_owned()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            guids,
+            [ids[1], ids[0], ids[2]]
+                .iter()
+                .map(|id| format!("{}/api/facts/{id}", state.public_url))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn tag_feed(pool: PgPool) {
+        let mut ids = Vec::new();
+
+        for (created_at, tag) in [
+            ("2024-01-02T00:00:00Z", "science"),
+            ("2024-01-03T00:00:00Z", "health"),
+            ("2024-01-04T00:00:00Z", "science"),
+        ] {
+            let entity = Faker.fake::<Fact>();
+
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body, created_at) VALUES ($1, $2, $3::timestamptz) RETURNING id",
+                Into::<String>::into(entity.title().to_owned()),
+                Into::<String>::into(entity.body().to_owned()),
+                created_at.parse::<DateTime<Utc>>().unwrap(),
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            query!(
+                "INSERT INTO fact_tags (fact_id, tag) VALUES ($1, $2)",
+                id,
+                tag
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+        let router = router.with_state(state.clone());
+        let guids = |tag: &'static str| {
+            let router = router.clone();
+            async move {
+                let raw_response = router
+                    .oneshot(
+                        Request::builder()
+                            .method(Method::GET)
+                            .uri(format!("/feed/{tag}.xml"))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+
+                assert_eq!(raw_response.status(), StatusCode::OK);
+                assert_eq!(raw_response.headers()[CONTENT_TYPE], "application/rss+xml");
+
+                let raw_feed = String::from_utf8(
+                    raw_response
+                        .into_body()
+                        .collect()
+                        .await
+                        .unwrap()
+                        .to_bytes()
+                        .to_vec(),
+                )
+                .unwrap();
+                let feed = roxmltree::Document::parse(&raw_feed).unwrap();
+                feed.descendants()
+                    .filter(|node| node.has_tag_name("guid"))
+                    .map(|guid| guid.text().unwrap().to_owned())
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(
+            guids("science").await,
+            [ids[2], ids[0]]
+                .iter()
+                .map(|id| format!("{}/api/facts/{id}", state.public_url))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            guids("health").await,
+            vec![format!("{}/api/facts/{}", state.public_url, ids[1])]
+        );
+        assert!(guids("history").await.is_empty());
+
+        let raw_response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/feed/science")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(
+        migrations = "./src/facts/migrations",
+        fixtures(path = "fixtures", scripts("truncate_facts_table"))
+    )]
+    async fn feed_walk(pool: PgPool) {
+        let mut ids = Vec::new();
+
+        for _ in 0..5 {
+            let entity = Faker.fake::<Fact>();
+
+            let id = query_scalar!(
+                "INSERT INTO facts (title, body) VALUES ($1, $2) RETURNING id",
+                Into::<String>::into(entity.title().to_owned()),
+                Into::<String>::into(entity.body().to_owned())
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+            ids.push(id);
+        }
+
+        let state = AppState {
+            facts: Arc::new(SqlxFactsRepository::new(pool)),
+            ..Default::default()
+        };
+        let mut uri = "/feed".to_owned();
+        let mut visited = Vec::new();
+
+        for _ in 0..ids.len() * 2 {
+            let router: Router<AppState> = AppRouter::new(state.clone()).into();
+            let raw_response = router
+                .with_state(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(&uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(raw_response.status(), StatusCode::OK);
+
+            let response = from_slice::<HttpFeedResponse>(
+                &raw_response.into_body().collect().await.unwrap().to_bytes(),
+            )
+            .unwrap();
+            visited.push(response.fact().id());
+            uri = format!("/feed?token={}", response.next_token());
+        }
+
+        ids.sort_unstable();
+        for cycle in visited.chunks_mut(ids.len()) {
+            cycle.sort_unstable();
+            assert_eq!(cycle, ids);
+        }
+    }
+
+    #[tokio::test]
+    async fn feed_invalid_token() {
+        let state = AppState::default();
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/feed?token=garbage")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn events_disabled() {
+        let state = AppState::default();
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        let raw_response = router
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/events")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(raw_response.status(), StatusCode::NOT_FOUND);
     }
 }
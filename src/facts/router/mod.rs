@@ -1,7 +1,26 @@
+pub use admin::AdminRouter;
+pub use auto_tags::AutoTagRules;
+#[cfg(feature = "graphql")]
+pub use graphql::GraphQLRouter;
+#[cfg(feature = "grpc")]
+pub use grpc::GrpcFacts;
 pub use handlers::AppRouter;
-pub use state::AppState;
+pub use profanity::{ProfanityFilter, ProfanityMode};
+pub use state::{AppState, Endpoints};
 
+mod admin;
+mod auto_tags;
+mod card;
 mod errors;
+mod feed;
+mod format;
+#[cfg(feature = "graphql")]
+mod graphql;
+#[cfg(feature = "grpc")]
+mod grpc;
 mod handlers;
 mod models;
+mod profanity;
+mod service;
 mod state;
+mod timing;
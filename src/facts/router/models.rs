@@ -1,11 +1,27 @@
+use std::{fmt, str::FromStr};
+
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{request::Parts, StatusCode},
+};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::errors::AppError;
 use crate::facts::repository::{
-    CreateFactRequest,
-    CreateFactRequestError,
     Fact,
     FactBody,
+    FactId,
+    FactTag,
+    FactTagError,
     FactTitle,
+    FindFactsRequest,
+    InvalidFact,
+    ListFactsRequest,
+    ListFactsRequestError,
+    MigrationStatus,
+    PoolStats,
+    TagMatch,
 };
 
 #[derive(Debug, Serialize)]
@@ -14,6 +30,11 @@ pub struct HttpFactResponse {
     id: i32,
     title: String,
     body: String,
+    created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_by: Option<String>,
 }
 
 #[cfg(test)]
@@ -29,43 +50,794 @@ impl HttpFactResponse {
     pub fn body(&self) -> &str {
         &self.body
     }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
 }
 
-impl From<Fact> for HttpFactResponse {
-    fn from(value: Fact) -> Self {
+impl HttpFactResponse {
+    pub fn new(value: &Fact, expose_actors: bool) -> Self {
+        let actor = |name: Option<&str>| name.filter(|_| expose_actors).map(ToOwned::to_owned);
+
         HttpFactResponse {
             id: value.id().into(),
             title: value.title().to_owned().into(),
             body: value.body().to_owned().into(),
+            created_at: value.created_at(),
+            created_by: actor(value.created_by()),
+            updated_by: actor(value.updated_by()),
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
-#[cfg_attr(test, derive(Serialize, PartialEq, Eq))]
-pub struct HttpCreateFactRequestBody {
+#[derive(Debug, Serialize)]
+pub struct JsonApiDocument<T, M = JsonApiMeta> {
+    data: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meta: Option<M>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonApiMeta {
+    total: u64,
+}
+
+impl<T> JsonApiDocument<T> {
+    pub fn new(data: T) -> Self {
+        Self { data, meta: None }
+    }
+
+    pub fn with_total(mut self, total: u64) -> Self {
+        self.meta = Some(JsonApiMeta { total });
+        self
+    }
+
+    pub fn with_meta<M>(self, meta: M) -> JsonApiDocument<T, M> {
+        JsonApiDocument {
+            data: self.data,
+            meta: Some(meta),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonApiFactResource {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    id: String,
+    attributes: JsonApiFactAttributes,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonApiFactAttributes {
     title: String,
     body: String,
+    created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_by: Option<String>,
+}
+
+impl From<HttpFactResponse> for JsonApiFactResource {
+    fn from(value: HttpFactResponse) -> Self {
+        JsonApiFactResource {
+            kind: "facts",
+            id: value.id.to_string(),
+            attributes: JsonApiFactAttributes {
+                title: value.title,
+                body: value.body,
+                created_at: value.created_at,
+                created_by: value.created_by,
+                updated_by: value.updated_by,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonLdFact {
+    #[serde(rename = "@context")]
+    context: &'static str,
+    #[serde(rename = "@type")]
+    kind: &'static str,
+    #[serde(rename = "@id")]
+    id: String,
+    headline: String,
+    text: String,
+    #[serde(rename = "datePublished")]
+    date_published: DateTime<Utc>,
+}
+
+impl JsonLdFact {
+    pub fn new(fact: &Fact, url: String) -> Self {
+        Self {
+            context: "https://schema.org",
+            kind: "Article",
+            id: url,
+            headline: fact.title().to_owned().into(),
+            text: fact.body().to_owned().into(),
+            date_published: fact.created_at(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize, PartialEq, Eq))]
+pub struct HttpMigrationStatusResponse {
+    applied: Vec<i64>,
+    pending: Vec<i64>,
+}
+
+#[cfg(test)]
+impl HttpMigrationStatusResponse {
+    pub fn pending(&self) -> &[i64] {
+        &self.pending
+    }
+}
+
+impl From<MigrationStatus> for HttpMigrationStatusResponse {
+    fn from(value: MigrationStatus) -> Self {
+        Self {
+            applied: value.applied().to_vec(),
+            pending: value.pending().to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct HttpPoolStatsResponse {
+    size: u32,
+    idle: u32,
+    in_use: u32,
+}
+
+impl From<PoolStats> for HttpPoolStatsResponse {
+    fn from(value: PoolStats) -> Self {
+        Self {
+            size: value.size(),
+            idle: value.idle(),
+            in_use: value.in_use(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct HttpDetailedHealthResponse {
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pool: Option<HttpPoolStatsResponse>,
+}
+
+impl HttpDetailedHealthResponse {
+    pub fn new(status: &'static str, pool: Option<PoolStats>) -> Self {
+        Self {
+            status,
+            pool: pool.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize, PartialEq, Eq))]
+pub struct HttpTagCountResponse {
+    tag: String,
+    count: u64,
+}
+
+impl From<(FactTag, u64)> for HttpTagCountResponse {
+    fn from((tag, count): (FactTag, u64)) -> Self {
+        Self {
+            tag: tag.into(),
+            count,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct HttpDayCountResponse {
+    date: NaiveDate,
+    count: u64,
+}
+
+impl From<(NaiveDate, u64)> for HttpDayCountResponse {
+    fn from((date, count): (NaiveDate, u64)) -> Self {
+        Self { date, count }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize, PartialEq, Eq))]
+pub struct HttpLikesResponse {
+    likes: u64,
+}
+
+#[cfg(test)]
+impl HttpLikesResponse {
+    pub fn likes(&self) -> u64 {
+        self.likes
+    }
+}
+
+impl From<u64> for HttpLikesResponse {
+    fn from(likes: u64) -> Self {
+        Self { likes }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize, PartialEq, Eq))]
+pub struct HttpDigestResponse {
+    digest: String,
+}
+
+#[cfg(test)]
+impl HttpDigestResponse {
+    pub fn digest(&self) -> &str {
+        &self.digest
+    }
+}
+
+impl From<String> for HttpDigestResponse {
+    fn from(digest: String) -> Self {
+        Self { digest }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize, PartialEq, Eq))]
+pub struct HttpExtremesResponse {
+    longest: HttpFactResponse,
+    shortest: HttpFactResponse,
+}
+
+#[cfg(test)]
+impl HttpExtremesResponse {
+    pub fn longest(&self) -> &HttpFactResponse {
+        &self.longest
+    }
+
+    pub fn shortest(&self) -> &HttpFactResponse {
+        &self.shortest
+    }
+}
+
+impl HttpExtremesResponse {
+    pub fn new((longest, shortest): &(Fact, Fact), expose_actors: bool) -> Self {
+        Self {
+            longest: HttpFactResponse::new(longest, expose_actors),
+            shortest: HttpFactResponse::new(shortest, expose_actors),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct HttpLimitsResponse {
+    max_title_length: usize,
+    min_body_length: usize,
+    max_body_length: usize,
+    max_tag_length: usize,
+    max_list_limit: u32,
+    max_search_results: u32,
+}
+
+impl HttpLimitsResponse {
+    pub fn new(min_body_length: usize, max_search_results: u32) -> Self {
+        Self {
+            max_title_length: FactTitle::MAX_LENGTH,
+            min_body_length: min_body_length.max(FactBody::DEFAULT_MIN_LENGTH),
+            max_body_length: FactBody::MAX_LENGTH,
+            max_tag_length: FactTag::MAX_LENGTH,
+            max_list_limit: ListFactsRequest::MAX_LIMIT,
+            max_search_results,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize, PartialEq, Eq))]
+pub struct HttpResyncSequenceResponse {
+    next_id: i32,
+}
+
+#[cfg(test)]
+impl HttpResyncSequenceResponse {
+    pub fn next_id(&self) -> i32 {
+        self.next_id
+    }
+}
+
+impl From<FactId> for HttpResyncSequenceResponse {
+    fn from(value: FactId) -> Self {
+        Self {
+            next_id: value.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize, PartialEq, Eq))]
+pub struct HttpInvalidFactResponse {
+    id: i32,
+    reasons: Vec<String>,
+}
+
+impl From<InvalidFact> for HttpInvalidFactResponse {
+    fn from(value: InvalidFact) -> Self {
+        Self {
+            id: value.id(),
+            reasons: value.reasons().to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize, PartialEq, Eq))]
+pub struct HttpValidationReportResponse {
+    invalid: Vec<HttpInvalidFactResponse>,
+}
+
+#[cfg(test)]
+impl HttpValidationReportResponse {
+    pub fn invalid_ids(&self) -> Vec<i32> {
+        self.invalid.iter().map(|fact| fact.id).collect()
+    }
+}
+
+impl From<Vec<InvalidFact>> for HttpValidationReportResponse {
+    fn from(value: Vec<InvalidFact>) -> Self {
+        Self {
+            invalid: value.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeedToken {
+    seed: i64,
+    position: u32,
+}
+
+impl FeedToken {
+    pub fn new(seed: i64) -> Self {
+        Self { seed, position: 0 }
+    }
+
+    pub fn seed(self) -> i64 {
+        self.seed
+    }
+
+    pub fn position(self) -> u32 {
+        self.position
+    }
+
+    pub fn next(self) -> Self {
+        Self {
+            seed: self.seed,
+            position: self.position.saturating_add(1),
+        }
+    }
+}
+
+impl fmt::Display for FeedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}{:08x}", self.seed, self.position)
+    }
+}
+
+impl FromStr for FeedToken {
+    type Err = AppError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let invalid = || AppError {
+            status_code: StatusCode::BAD_REQUEST,
+            details: format!("Feed token '{raw}' is invalid"),
+        };
+
+        if raw.len() != 24 || !raw.is_ascii() {
+            return Err(invalid());
+        }
+        let (seed, position) = raw.split_at(16);
+
+        Ok(Self {
+            seed: u64::from_str_radix(seed, 16)
+                .map_err(|_| invalid())?
+                .cast_signed(),
+            position: u32::from_str_radix(position, 16).map_err(|_| invalid())?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HttpFeedQuery {
+    token: Option<String>,
+}
+
+impl HttpFeedQuery {
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize, PartialEq, Eq))]
+pub struct HttpFeedResponse {
+    fact: HttpFactResponse,
+    next_token: String,
+}
+
+impl HttpFeedResponse {
+    pub fn new(fact: &Fact, next_token: FeedToken, expose_actors: bool) -> Self {
+        Self {
+            fact: HttpFactResponse::new(fact, expose_actors),
+            next_token: next_token.to_string(),
+        }
+    }
 }
 
 #[cfg(test)]
+impl HttpFeedResponse {
+    pub fn fact(&self) -> &HttpFactResponse {
+        &self.fact
+    }
+
+    pub fn next_token(&self) -> &str {
+        &self.next_token
+    }
+}
+
+const RANDOM_INCLUDES: [&str; 2] = ["neighbors", "count"];
+
+#[derive(Debug, Deserialize)]
+pub struct HttpRandomQuery {
+    max_length: Option<usize>,
+    include: Option<String>,
+    bias: Option<String>,
+}
+
+impl HttpRandomQuery {
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    pub fn includes(&self) -> Result<RandomIncludes, AppError> {
+        let mut includes = RandomIncludes::default();
+        let Some(raw) = &self.include else {
+            return Ok(includes);
+        };
+
+        for include in raw.split(',').map(str::trim) {
+            match include {
+                "neighbors" => includes.neighbors = true,
+                "count" => includes.count = true,
+                _ => {
+                    return Err(AppError {
+                        status_code: StatusCode::BAD_REQUEST,
+                        details: format!(
+                            "Unknown include {include:?}, expected some of {}",
+                            RANDOM_INCLUDES.join(", ")
+                        ),
+                    })
+                }
+            }
+        }
+
+        Ok(includes)
+    }
+
+    pub fn bias(&self) -> Result<RandomBias, AppError> {
+        match self.bias.as_deref().map(str::trim) {
+            None | Some("uniform") => Ok(RandomBias::Uniform),
+            Some("recency") => Ok(RandomBias::Recency),
+            Some(other) => Err(AppError {
+                status_code: StatusCode::BAD_REQUEST,
+                details: format!("Unknown bias {other:?}, expected uniform or recency"),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RandomBias {
+    #[default]
+    Uniform,
+    Recency,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RandomIncludes {
+    neighbors: bool,
+    count: bool,
+}
+
+impl RandomIncludes {
+    pub fn neighbors(self) -> bool {
+        self.neighbors
+    }
+
+    pub fn count(self) -> bool {
+        self.count
+    }
+
+    pub fn any(self) -> bool {
+        self.neighbors || self.count
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize, PartialEq, Eq))]
+pub struct HttpNeighborsResponse {
+    previous: Option<i32>,
+    next: Option<i32>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[cfg_attr(test, derive(Deserialize, PartialEq, Eq))]
+pub struct HttpRandomFactMeta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    neighbors: Option<HttpNeighborsResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<u64>,
+}
+
+impl HttpRandomFactMeta {
+    pub fn with_neighbors(mut self, (previous, next): (Option<FactId>, Option<FactId>)) -> Self {
+        self.neighbors = Some(HttpNeighborsResponse {
+            previous: previous.map(Into::into),
+            next: next.map(Into::into),
+        });
+        self
+    }
+
+    pub fn with_count(mut self, count: u64) -> Self {
+        self.count = Some(count);
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HttpChangesQuery {
+    since: DateTime<Utc>,
+}
+
+impl HttpChangesQuery {
+    pub fn since(&self) -> DateTime<Utc> {
+        self.since
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HttpRecentQuery {
+    minutes: u32,
+}
+
+impl HttpRecentQuery {
+    pub fn minutes(&self) -> u32 {
+        self.minutes
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HttpCountByDayQuery {
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
+}
+
+impl HttpCountByDayQuery {
+    pub fn created_after(&self) -> Option<DateTime<Utc>> {
+        self.created_after
+    }
+
+    pub fn created_before(&self) -> Option<DateTime<Utc>> {
+        self.created_before
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HttpListFactsQuery {
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
+    created_by: Option<String>,
+    tags: Option<String>,
+    #[serde(rename = "match")]
+    tag_match: Option<String>,
+}
+
+impl HttpListFactsQuery {
+    pub fn tag_filter(&self) -> Result<Option<(Vec<FactTag>, TagMatch)>, AppError> {
+        let mode = match self.tag_match.as_deref().map(str::trim) {
+            None | Some("any") => TagMatch::Any,
+            Some("all") => TagMatch::All,
+            Some(other) => {
+                return Err(AppError {
+                    status_code: StatusCode::BAD_REQUEST,
+                    details: format!("Unknown tag match {other:?}, expected any or all"),
+                })
+            }
+        };
+        let Some(raw) = &self.tags else {
+            return Ok(None);
+        };
+        let tags = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(FactTag::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((!tags.is_empty()).then_some((tags, mode)))
+    }
+
+    pub fn into_request(
+        self,
+        pagination: Pagination,
+    ) -> Result<ListFactsRequest, ListFactsRequestError> {
+        Ok(ListFactsRequest::new(
+            pagination.limit.unwrap_or(ListFactsRequest::DEFAULT_LIMIT),
+            pagination.offset.unwrap_or_default(),
+        )?
+        .with_created_after(self.created_after)
+        .with_created_before(self.created_before)
+        .with_created_by(self.created_by))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PaginationQuery {
+    limit: Option<String>,
+    offset: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pagination {
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+impl Pagination {
+    fn parse(name: &str, raw: Option<&str>) -> Result<Option<u32>, AppError> {
+        let Some(raw) = raw.map(str::trim) else {
+            return Ok(None);
+        };
+
+        raw.parse().map(Some).map_err(|_| {
+            let negative = raw
+                .strip_prefix('-')
+                .is_some_and(|digits| digits.parse::<u64>().is_ok());
+
+            AppError {
+                status_code: StatusCode::BAD_REQUEST,
+                details: if negative {
+                    format!("Invalid {name} {raw}, must not be negative")
+                } else {
+                    format!(
+                        "Invalid {name} {raw:?}, expected an integer between 0 and {}",
+                        u32::MAX
+                    )
+                },
+            }
+        })
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Pagination {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let Query(query) =
+            Query::<PaginationQuery>::try_from_uri(&parts.uri).map_err(|err| AppError {
+                status_code: StatusCode::BAD_REQUEST,
+                details: err.body_text(),
+            })?;
+
+        Ok(Self {
+            limit: Self::parse("limit", query.limit.as_deref())?,
+            offset: Self::parse("offset", query.offset.as_deref())?,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Actor(String);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Audience {
+    Anonymous,
+    Authenticated,
+}
+
+impl Actor {
+    pub fn new(name: &str) -> Self {
+        Self(name.to_owned())
+    }
+
+    pub fn name(&self) -> Option<String> {
+        if self.0.is_empty() {
+            None
+        } else {
+            Some(self.0.clone())
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct HttpCreateFactRequestBody {
+    title: FactTitle,
+    body: FactBody,
+    #[serde(default)]
+    public: Option<bool>,
+}
+
 impl HttpCreateFactRequestBody {
-    pub fn title(&self) -> &str {
+    pub fn title(&self) -> &FactTitle {
         &self.title
     }
 
-    pub fn body(&self) -> &str {
+    pub fn body(&self) -> &FactBody {
         &self.body
     }
+
+    pub fn public(&self) -> bool {
+        self.public.unwrap_or(true)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct HttpMergePatchFactBody {
+    title: Option<FactTitle>,
+    body: Option<FactBody>,
+}
+
+impl HttpMergePatchFactBody {
+    pub fn merge_into(self, fact: &Fact) -> HttpCreateFactRequestBody {
+        HttpCreateFactRequestBody {
+            title: self.title.unwrap_or_else(|| fact.title().to_owned()),
+            body: self.body.unwrap_or_else(|| fact.body().to_owned()),
+            public: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HttpFindFactsRequestBody {
+    title_contains: Option<String>,
+    body_contains: Option<String>,
+    tag: Option<String>,
+    limit: Option<u32>,
+}
+
+impl HttpFindFactsRequestBody {
+    pub fn limit(&self) -> Option<u32> {
+        self.limit
+    }
+}
+
+impl TryFrom<HttpFindFactsRequestBody> for FindFactsRequest {
+    type Error = AppError;
+
+    fn try_from(value: HttpFindFactsRequestBody) -> Result<Self, Self::Error> {
+        let tag = value.tag.as_deref().map(FactTag::new).transpose()?;
+
+        Ok(FindFactsRequest::new(
+            value.title_contains,
+            value.body_contains,
+            tag,
+        )?)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(test, derive(Serialize, PartialEq, Eq))]
+pub struct HttpAddFactTagRequestBody {
+    tag: String,
 }
 
-impl TryFrom<HttpCreateFactRequestBody> for CreateFactRequest {
-    type Error = CreateFactRequestError;
+impl TryFrom<HttpAddFactTagRequestBody> for FactTag {
+    type Error = FactTagError;
 
-    fn try_from(value: HttpCreateFactRequestBody) -> Result<Self, Self::Error> {
-        Ok(CreateFactRequest::new(
-            &FactTitle::new(&value.title)?,
-            &FactBody::new(&value.body)?,
-        ))
+    fn try_from(value: HttpAddFactTagRequestBody) -> Result<Self, Self::Error> {
+        FactTag::new(&value.tag)
     }
 }
@@ -0,0 +1,145 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+use tokio::fs;
+
+use crate::facts::repository::CreateFactRequestError;
+
+#[derive(Error, Debug)]
+pub enum ProfanityFilterError {
+    #[error("Cannot access {path:?}: {inner}")]
+    Io { path: PathBuf, inner: String },
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Body contains a disallowed word")]
+pub struct ProfanityError;
+
+impl From<ProfanityError> for CreateFactRequestError {
+    fn from(value: ProfanityError) -> Self {
+        CreateFactRequestError::InvalidBody {
+            inner: value.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProfanityMode {
+    #[default]
+    Reject,
+    Mask,
+}
+
+#[derive(Debug, Default)]
+pub struct ProfanityFilter {
+    words: HashSet<String>,
+    mode: ProfanityMode,
+}
+
+impl ProfanityFilter {
+    pub async fn load(path: &Path, mode: ProfanityMode) -> Result<Self, ProfanityFilterError> {
+        let content = fs::read_to_string(path)
+            .await
+            .map_err(|err| ProfanityFilterError::Io {
+                path: path.to_owned(),
+                inner: err.to_string(),
+            })?;
+
+        Ok(Self::new(
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.starts_with('#')),
+            mode,
+        ))
+    }
+
+    pub fn new<'a>(words: impl IntoIterator<Item = &'a str>, mode: ProfanityMode) -> Self {
+        let words = words
+            .into_iter()
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+
+        Self { words, mode }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn apply(&self, body: &str) -> Result<String, ProfanityError> {
+        let mut filtered = String::with_capacity(body.len());
+        let mut rest = body;
+
+        while let Some(start) = rest.find(char::is_alphanumeric) {
+            let (before, word) = rest.split_at(start);
+            let end = word
+                .find(|c: char| !c.is_alphanumeric())
+                .unwrap_or(word.len());
+            let (word, after) = word.split_at(end);
+            filtered.push_str(before);
+
+            if !self.words.contains(&word.to_lowercase()) {
+                filtered.push_str(word);
+            } else if self.mode == ProfanityMode::Mask {
+                filtered.extend(word.chars().map(|_| '*'));
+            } else {
+                return Err(ProfanityError);
+            }
+            rest = after;
+        }
+        filtered.push_str(rest);
+
+        Ok(filtered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use super::*;
+
+    #[test]
+    fn reject() {
+        let filter = ProfanityFilter::new(["darn", "Heck"], ProfanityMode::Reject);
+
+        assert_eq!(filter.apply("Well, DARN it!"), Err(ProfanityError));
+        assert_eq!(filter.apply("what the heck"), Err(ProfanityError));
+        assert_eq!(
+            filter.apply("Darned hecks are whole words").unwrap(),
+            "Darned hecks are whole words"
+        );
+    }
+
+    #[test]
+    fn mask() {
+        let filter = ProfanityFilter::new(["darn", "heck"], ProfanityMode::Mask);
+
+        assert_eq!(
+            filter.apply("Well, DARN it! Heck-no, darned").unwrap(),
+            "Well, **** it! ****-no, darned"
+        );
+        assert_eq!(filter.apply("").unwrap(), "");
+    }
+
+    #[tokio::test]
+    async fn load_from_file() {
+        let path = temp_dir().join(format!("profanity-{}.txt", std::process::id()));
+        fs::write(&path, "# family friendly\ndarn\n\n  HECK \n")
+            .await
+            .unwrap();
+
+        let filter = ProfanityFilter::load(&path, ProfanityMode::Mask)
+            .await
+            .unwrap();
+        fs::remove_file(&path).await.unwrap();
+
+        assert_eq!(filter.len(), 2);
+        assert_eq!(filter.apply("heck").unwrap(), "****");
+    }
+}
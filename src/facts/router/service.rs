@@ -0,0 +1,60 @@
+use axum::http::StatusCode;
+
+use super::{errors::AppError, handlers::audit_write, models::Actor, state::AppState};
+use crate::facts::repository::{
+    CreateFactRequest,
+    CreateFactRequestError,
+    Fact,
+    FactBody,
+    FactTitle,
+};
+
+pub fn disabled(feature: &str) -> AppError {
+    AppError {
+        status_code: StatusCode::NOT_FOUND,
+        details: format!("{feature} is disabled"),
+    }
+}
+
+pub fn validate_body(state: &AppState, raw: &str) -> Result<FactBody, CreateFactRequestError> {
+    let mut body = FactBody::sanitize(raw, state.control_characters)?;
+    if let Some(filter) = &state.profanity {
+        body = filter.apply(&body)?;
+    }
+
+    Ok(FactBody::with_min_length(&body, state.min_body_length)?)
+}
+
+pub async fn create_fact(
+    state: &AppState,
+    actor: &Actor,
+    title: &FactTitle,
+    body: &str,
+    public: bool,
+    if_absent: bool,
+) -> Result<Fact, AppError> {
+    let body = validate_body(state, body)?;
+    let tags = state
+        .auto_tags
+        .as_ref()
+        .map(|rules| rules.tags_for(&String::from(body.clone())))
+        .unwrap_or_default();
+    let request = CreateFactRequest::new(title, &body)
+        .with_public(public)
+        .with_created_by(actor.name())
+        .with_unique_body(state.reject_duplicate_bodies)
+        .with_max_total(state.max_total_facts)
+        .with_tags(tags);
+
+    let result = if if_absent {
+        state.facts.create_if_absent(&request).await?
+    } else {
+        state.facts.create(&request).await?
+    };
+    audit_write(state, actor, result.id(), "created");
+    for _ in request.tags() {
+        audit_write(state, actor, result.id(), "tagged");
+    }
+
+    Ok(result)
+}
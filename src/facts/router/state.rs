@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::sync::{atomic::AtomicBool, Arc};
 
 #[cfg(test)]
 use argon2::{
@@ -6,15 +6,52 @@ use argon2::{
     Argon2,
     PasswordHasher,
 };
+use tokio::sync::broadcast::Sender;
 
-use crate::facts::FactsRepository;
+use super::{AutoTagRules, ProfanityFilter};
 #[cfg(test)]
 use crate::facts::MockedFactsRepository;
+use crate::facts::{ControlCharacters, FactsRepository};
 
 #[derive(Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct AppState {
     pub facts: Arc<dyn FactsRepository>,
     pub auth_key: String,
+    pub draining: Arc<AtomicBool>,
+    pub public_url: String,
+    pub changes: Option<Sender<String>>,
+    pub max_search_results: u32,
+    pub min_body_length: usize,
+    pub control_characters: ControlCharacters,
+    pub log_writes: bool,
+    pub server_timing: bool,
+    pub expose_actors: bool,
+    pub idempotent_delete: bool,
+    pub reject_duplicate_bodies: bool,
+    pub max_total_facts: Option<u64>,
+    pub disable_random: bool,
+    pub auto_tags: Option<Arc<AutoTagRules>>,
+    pub profanity: Option<Arc<ProfanityFilter>>,
+    pub endpoints: Endpoints,
+}
+
+#[derive(Clone, Copy)]
+pub struct Endpoints {
+    pub create: bool,
+    pub delete: bool,
+    pub search: bool,
+}
+
+#[cfg(test)]
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            create: true,
+            delete: true,
+            search: true,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -26,6 +63,22 @@ impl Default for AppState {
                 .hash_password(&[], &SaltString::generate(&mut OsRng))
                 .unwrap()
                 .to_string(),
+            draining: Arc::default(),
+            public_url: "http://localhost:8080".to_owned(),
+            changes: None,
+            max_search_results: 50,
+            min_body_length: 1,
+            control_characters: ControlCharacters::default(),
+            log_writes: false,
+            server_timing: false,
+            expose_actors: false,
+            idempotent_delete: false,
+            reject_duplicate_bodies: false,
+            max_total_facts: None,
+            disable_random: false,
+            auto_tags: None,
+            profanity: None,
+            endpoints: Endpoints::default(),
         }
     }
 }
@@ -0,0 +1,101 @@
+use std::{future::Future, time::Duration};
+
+use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response, Extension};
+use tokio::time::Instant;
+
+const SERVER_TIMING_HEADER: &str = "server-timing";
+
+#[derive(Clone, Copy, Debug)]
+pub struct StorageTiming(Duration);
+
+pub async fn timed<F: Future>(future: F) -> (F::Output, Extension<StorageTiming>) {
+    let started = Instant::now();
+    let output = future.await;
+
+    (output, Extension(StorageTiming(started.elapsed())))
+}
+
+fn milliseconds(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+pub async fn server_timing_middleware(request: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let mut response = next.run(request).await;
+
+    let Some(StorageTiming(storage)) = response.extensions().get::<StorageTiming>().copied() else {
+        return response;
+    };
+    let metrics = format!(
+        "db;dur={:.3}, total;dur={:.3}",
+        milliseconds(storage),
+        milliseconds(started.elapsed())
+    );
+    if let Ok(value) = HeaderValue::from_str(&metrics) {
+        response.headers_mut().insert(SERVER_TIMING_HEADER, value);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::StatusCode, Router};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::facts::{AppRouter, AppState};
+
+    async fn call(server_timing: bool, uri: &str) -> Response {
+        let state = AppState {
+            server_timing,
+            ..Default::default()
+        };
+        let router: Router<AppState> = AppRouter::new(state.clone()).into();
+
+        router
+            .with_state(state)
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    fn parse(header: &str) -> Vec<(&str, f64)> {
+        header
+            .split(',')
+            .map(|metric| {
+                let (name, duration) = metric.trim().split_once(";dur=").unwrap();
+                (name, duration.parse().unwrap())
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn enabled() {
+        let response = call(true, "/42").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let metrics = parse(response.headers()[SERVER_TIMING_HEADER].to_str().unwrap());
+
+        assert_eq!(
+            metrics.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+            ["db", "total"]
+        );
+        assert!(metrics[0].1 <= metrics[1].1);
+    }
+
+    #[tokio::test]
+    async fn disabled_by_default() {
+        let response = call(false, "/42").await;
+
+        assert!(response.headers().get(SERVER_TIMING_HEADER).is_none());
+    }
+
+    #[tokio::test]
+    async fn only_on_reads() {
+        let response = call(true, "/health").await;
+
+        assert!(response.headers().get(SERVER_TIMING_HEADER).is_none());
+    }
+}
@@ -0,0 +1,97 @@
+use askama::Template;
+use axum::{
+    http::StatusCode,
+    response::{Html, IntoResponse},
+};
+use tracing::error;
+
+const TITLE: &str = "About smoking";
+const BODY: &str = r#"The phrase "smoking kills" is a direct statement about the severe health risks of tobacco use
+Smoking is a leading cause of preventable death globally, leading to cancer, heart disease, stroke, and lung diseases like emphysema"#;
+
+#[derive(Template)]
+#[template(path = "facts.html")]
+struct FactPage<'a> {
+    id: i32,
+    title: &'a str,
+    body: &'a str,
+}
+
+pub async fn home() -> impl IntoResponse {
+    let page = FactPage {
+        id: 1,
+        title: TITLE,
+        body: BODY,
+    };
+
+    match page.render() {
+        Ok(page) => (StatusCode::OK, Html(page)).into_response(),
+        Err(err) => {
+            error!("Cannot render home page: {err}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, extract::Request, routing::get, Router};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[test]
+    fn renders_fact() {
+        let page = FactPage {
+            id: 7,
+            title: "Octopuses",
+            body: "Octopuses have three hearts\nand blue blood",
+        }
+        .render()
+        .unwrap();
+
+        assert!(page.contains("Fact number 7: Octopuses"));
+        assert!(page.contains("Octopuses have three hearts</br>"));
+        assert!(page.contains("and blue blood"));
+    }
+
+    #[test]
+    fn escapes_fact() {
+        let page = FactPage {
+            id: 1,
+            title: "<script>",
+            body: "a & b",
+        }
+        .render()
+        .unwrap();
+
+        assert!(!page.contains("<script>"));
+        assert!(page.contains("a &#38; b"));
+    }
+
+    #[tokio::test]
+    async fn home_page() {
+        let response = Router::new()
+            .route("/", get(home))
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let page = String::from_utf8(
+            response
+                .into_body()
+                .collect()
+                .await
+                .unwrap()
+                .to_bytes()
+                .to_vec(),
+        )
+        .unwrap();
+
+        assert!(page.contains(TITLE));
+        assert!(page.contains("severe health risks of tobacco use"));
+    }
+}
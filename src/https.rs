@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::{header::LOCATION, uri::Scheme, HeaderMap, HeaderName, StatusCode, Uri},
+    middleware::{from_fn_with_state, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+
+const FORWARDED_PROTO_HEADER: HeaderName = HeaderName::from_static("x-forwarded-proto");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HttpsEnforcement {
+    Redirect,
+    Reject,
+}
+
+#[derive(Clone)]
+struct RequireHttps {
+    enforcement: HttpsEnforcement,
+    exempt: Arc<[String]>,
+    host: Option<Arc<str>>,
+}
+
+pub fn require_https(
+    router: Router,
+    enforcement: HttpsEnforcement,
+    exempt: &[String],
+    public_url: &str,
+) -> Router {
+    router.layer(from_fn_with_state(
+        RequireHttps {
+            enforcement,
+            exempt: exempt.into(),
+            host: redirect_host(public_url).map(Into::into),
+        },
+        require_https_middleware,
+    ))
+}
+
+/// Redirects always target the configured public URL, never a host taken
+/// from the request. A port is only kept when the public URL is already
+/// HTTPS, since a plain HTTP port would not serve TLS.
+fn redirect_host(public_url: &str) -> Option<String> {
+    let uri = public_url.parse::<Uri>().ok()?;
+
+    if uri.scheme() == Some(&Scheme::HTTPS) {
+        uri.authority().map(ToString::to_string)
+    } else {
+        uri.host().map(ToOwned::to_owned)
+    }
+}
+
+fn first_value<'a>(headers: &'a HeaderMap, name: &HeaderName) -> Option<&'a str> {
+    headers
+        .get(name)?
+        .to_str()
+        .ok()?
+        .split(',')
+        .next()
+        .map(str::trim)
+}
+
+async fn require_https_middleware(
+    State(policy): State<RequireHttps>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let headers = request.headers();
+    let secure = first_value(headers, &FORWARDED_PROTO_HEADER)
+        .is_some_and(|proto| proto.eq_ignore_ascii_case("https"));
+
+    if secure
+        || policy
+            .exempt
+            .iter()
+            .any(|path| path == request.uri().path())
+    {
+        return next.run(request).await;
+    }
+
+    match (policy.enforcement, policy.host.as_deref()) {
+        (HttpsEnforcement::Redirect, Some(host)) => {
+            let location = format!(
+                "https://{host}{}",
+                request
+                    .uri()
+                    .path_and_query()
+                    .map_or("/", |path| path.as_str())
+            );
+
+            (StatusCode::PERMANENT_REDIRECT, [(LOCATION, location)]).into_response()
+        }
+        _ => (StatusCode::FORBIDDEN, "HTTPS is required").into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::header::HOST, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn router(enforcement: HttpsEnforcement) -> Router {
+        let router = Router::new()
+            .route("/api/random", get(|| async { StatusCode::OK }))
+            .route("/api/health", get(|| async { StatusCode::OK }));
+
+        require_https(
+            router,
+            enforcement,
+            &["/api/health".to_owned()],
+            "https://facts.example.com",
+        )
+    }
+
+    async fn call(router: Router, uri: &str, proto: Option<&str>) -> Response {
+        let mut request = Request::get(uri).header(HOST, "facts.example.com");
+        if let Some(proto) = proto {
+            request = request.header(FORWARDED_PROTO_HEADER, proto);
+        }
+
+        router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn redirect() {
+        let router = router(HttpsEnforcement::Redirect);
+
+        let response = call(router.clone(), "/api/random?format=csv", Some("http")).await;
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers()[LOCATION],
+            "https://facts.example.com/api/random?format=csv"
+        );
+        assert_eq!(
+            call(router.clone(), "/api/random", None).await.status(),
+            StatusCode::PERMANENT_REDIRECT
+        );
+        assert_eq!(
+            call(router, "/api/random", Some("https")).await.status(),
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn redirect_ignores_request_host() {
+        let response = router(HttpsEnforcement::Redirect)
+            .oneshot(
+                Request::get("/api/random")
+                    .header(HOST, "evil.example.com")
+                    .header("x-forwarded-host", "evil.example.com")
+                    .header(FORWARDED_PROTO_HEADER, "http")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.headers()[LOCATION],
+            "https://facts.example.com/api/random"
+        );
+    }
+
+    #[test]
+    fn redirect_host_from_public_url() {
+        assert_eq!(
+            redirect_host("https://facts.example.com:8443/").as_deref(),
+            Some("facts.example.com:8443")
+        );
+        assert_eq!(
+            redirect_host("http://localhost:8080").as_deref(),
+            Some("localhost")
+        );
+        assert_eq!(redirect_host("not a url"), None);
+    }
+
+    #[tokio::test]
+    async fn reject() {
+        let router = router(HttpsEnforcement::Reject);
+
+        assert_eq!(
+            call(router.clone(), "/api/random", Some("http"))
+                .await
+                .status(),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call(router.clone(), "/api/random", None).await.status(),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call(router, "/api/random", Some("HTTPS, http"))
+                .await
+                .status(),
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn exempt_paths() {
+        for enforcement in [HttpsEnforcement::Redirect, HttpsEnforcement::Reject] {
+            assert_eq!(
+                call(router(enforcement), "/api/health", Some("http"))
+                    .await
+                    .status(),
+                StatusCode::OK
+            );
+        }
+    }
+}
@@ -1,37 +1,358 @@
-use std::sync::Arc;
+#[cfg(feature = "grpc")]
+use std::net::{IpAddr, SocketAddr};
+use std::{path::Path, process::ExitCode, sync::Arc, time::Duration};
 
-use axum::{response::Html, routing::get, Router};
+use axum::{middleware::from_fn_with_state, routing::get, Router};
+use cache_control::{cache_control_middleware, RouteCacheMaxAge};
+use chaos::{inject_chaos, Chaos};
 use clap::Parser;
-use config::{Config, LogFormat, StorageType};
-use facts::{AppRouter, AppState, MockedFactsRepository, SqlxFactsRepository};
+use config::{
+    ChaosTesting,
+    Config,
+    ControlCharactersPolicy,
+    HttpsPolicy,
+    LogFormat,
+    Logging,
+    ProfanityPolicy,
+    RandomStrategyType,
+    Runtime,
+    Storage,
+    StorageType,
+    TrailingSlashPolicy,
+};
+use data_source::expose_data_source;
+#[cfg(feature = "graphql")]
+use facts::GraphQLRouter;
+#[cfg(feature = "grpc")]
+use facts::GrpcFacts;
+use facts::{
+    listen_changes,
+    with_session_settings,
+    AdminRouter,
+    AppRouter,
+    AppState,
+    AutoTagRules,
+    CircuitBreakerFactsRepository,
+    ControlCharacters,
+    Endpoints,
+    FactId,
+    FactsRepository,
+    JsonFileFactsRepository,
+    MinMaxId,
+    MockedFactsRepository,
+    OrderByRandom,
+    ProfanityFilter,
+    ProfanityMode,
+    RandomStrategy,
+    SqlxFactsRepository,
+    Weighted,
+};
+use home::home;
+use https::{require_https, HttpsEnforcement};
+use self_test::self_test;
+use server::Server;
 use sqlx::postgres::PgPoolOptions;
-use tokio::net::TcpListener;
+use timeouts::{timeout_middleware, RouteTimeouts};
+use tokio::{net::TcpListener, sync::broadcast::Sender};
 use tower_http::trace::TraceLayer;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, Layer};
+use trailing_slash::{redirect_trailing_slash, trim_trailing_slash};
 
+mod cache_control;
+mod chaos;
 mod config;
+mod data_source;
+#[cfg(feature = "sentry")]
+mod error_reporting;
 mod facts;
+mod home;
+mod https;
+mod self_test;
+mod server;
+#[cfg(test)]
+mod test_support;
+mod timeouts;
+mod trailing_slash;
 
 const TRACING_STARTUP_TARGET: &str = "startup";
 
-#[tokio::main]
-async fn main() {
-    let args = Config::parse();
+async fn facts_repository(storage: &Storage) -> (Arc<dyn FactsRepository>, Option<Sender<String>>) {
+    match storage.storage_type {
+        StorageType::Mocked => {
+            info!(target : TRACING_STARTUP_TARGET, "Using MockedRepository");
+            (Arc::new(MockedFactsRepository {}), None)
+        }
+        StorageType::JsonFile => {
+            info!(target : TRACING_STARTUP_TARGET, "Using JsonFileRepository at {:?}", &storage.storage_file);
+            let repository = JsonFileFactsRepository::open(
+                &storage.storage_file,
+                Duration::from_millis(storage.storage_file_debounce_ms),
+            )
+            .await
+            .inspect_err(|err| {
+                error!(
+                    target : TRACING_STARTUP_TARGET,
+                    "Cannot open facts file: {err}"
+                );
+            })
+            .unwrap();
+
+            (Arc::new(repository), None)
+        }
+        StorageType::Sqlx => {
+            info!(target : TRACING_STARTUP_TARGET, "Using SqlxRepository");
+
+            info!(target : TRACING_STARTUP_TARGET, "Creating pool for {:?}", &storage.storage_dsn);
+            if let Some(timeout) = storage.statement_timeout_ms {
+                info!(target : TRACING_STARTUP_TARGET, "Using {timeout}ms statement timeout");
+            }
+            let pool = with_session_settings(
+                PgPoolOptions::default()
+                    .acquire_timeout(Duration::from_millis(storage.storage_acquire_timeout_ms)),
+                storage.statement_timeout_ms.map(Duration::from_millis),
+                storage.storage_suppress_change_notifications,
+            )
+            .connect(&storage.storage_dsn)
+            .await
+            .inspect_err(|err| {
+                error!(
+                    target : TRACING_STARTUP_TARGET,
+                    "Cannot acquire pool: {err:?}"
+                );
+            })
+            .unwrap();
+
+            info!(target : TRACING_STARTUP_TARGET, "Using {:?} random strategy", &storage.storage_random_strategy);
+            let random_strategy: Arc<dyn RandomStrategy> = match storage.storage_random_strategy {
+                RandomStrategyType::OrderByRandom => Arc::new(OrderByRandom),
+                RandomStrategyType::MinMaxId => Arc::new(MinMaxId),
+                RandomStrategyType::Weighted => Arc::new(Weighted),
+            };
 
-    let subscriber_builder = tracing_subscriber::fmt().with_max_level(args.logging.log_level);
+            let repository =
+                SqlxFactsRepository::new(pool.clone()).random_strategy(random_strategy);
+
+            if let Some(start) = storage.storage_id_start {
+                info!(target : TRACING_STARTUP_TARGET, "Starting fact ids from {start}");
+                repository
+                    .start_ids_from(FactId::new(start).unwrap())
+                    .await
+                    .inspect_err(|err| {
+                        error!(
+                            target : TRACING_STARTUP_TARGET,
+                            "Cannot move the fact id sequence: {err:?}"
+                        );
+                    })
+                    .unwrap();
+            }
 
-    match args.logging.log_format {
-        LogFormat::Default => subscriber_builder.init(),
-        LogFormat::Json => subscriber_builder.json().init(),
-        LogFormat::Pretty => subscriber_builder.pretty().init(),
+            let changes = if storage.storage_change_stream {
+                info!(target : TRACING_STARTUP_TARGET, "Listening to the change stream");
+                Some(
+                    listen_changes(&pool)
+                        .await
+                        .inspect_err(|err| {
+                            error!(
+                                target : TRACING_STARTUP_TARGET,
+                                "Cannot listen to the change stream: {err:?}"
+                            );
+                        })
+                        .unwrap(),
+                )
+            } else {
+                None
+            };
+
+            let repository: Arc<dyn FactsRepository> = Arc::new(repository);
+            let repository = match storage.storage_breaker_threshold {
+                Some(threshold) => {
+                    info!(
+                        target : TRACING_STARTUP_TARGET,
+                        "Opening circuit breaker after {threshold} consecutive failures for {}ms",
+                        storage.storage_breaker_cooldown_ms
+                    );
+                    Arc::new(CircuitBreakerFactsRepository::new(
+                        repository,
+                        threshold,
+                        Duration::from_millis(storage.storage_breaker_cooldown_ms),
+                    ))
+                }
+                None => repository,
+            };
+
+            (repository, changes)
+        }
     }
+}
+
+async fn auto_tag_rules(path: Option<&Path>) -> Option<Arc<AutoTagRules>> {
+    let path = path?;
+    let rules = AutoTagRules::load(path)
+        .await
+        .inspect_err(|err| {
+            error!(
+                target : TRACING_STARTUP_TARGET,
+                "Cannot load auto-tagging rules: {err}"
+            );
+        })
+        .unwrap();
+    info!(target : TRACING_STARTUP_TARGET, "Using {} auto-tagging rules from {path:?}", rules.len());
+
+    Some(Arc::new(rules))
+}
 
+async fn profanity_filter(
+    path: Option<&Path>,
+    policy: &ProfanityPolicy,
+) -> Option<Arc<ProfanityFilter>> {
+    let path = path?;
+    let mode = match policy {
+        ProfanityPolicy::Reject => ProfanityMode::Reject,
+        ProfanityPolicy::Mask => ProfanityMode::Mask,
+    };
+    let filter = ProfanityFilter::load(path, mode)
+        .await
+        .inspect_err(|err| {
+            error!(
+                target : TRACING_STARTUP_TARGET,
+                "Cannot load profanity word list: {err}"
+            );
+        })
+        .unwrap();
+    info!(target : TRACING_STARTUP_TARGET, "Using {mode:?} profanity filter with {} words from {path:?}", filter.len());
+
+    Some(Arc::new(filter))
+}
+
+fn route_timeouts(runtime: &Runtime) -> RouteTimeouts {
+    runtime.route_timeout_ms.iter().fold(
+        RouteTimeouts::new(Duration::from_millis(runtime.request_timeout_ms)),
+        |timeouts, (path, timeout)| {
+            info!(target : TRACING_STARTUP_TARGET, "Using {timeout}ms timeout for {path:?}");
+            timeouts.with_route(path, Duration::from_millis(*timeout))
+        },
+    )
+}
+
+fn route_cache_max_age(runtime: &Runtime) -> RouteCacheMaxAge {
+    runtime.route_cache_max_age.iter().fold(
+        RouteCacheMaxAge::default(),
+        |max_age, (path, seconds)| {
+            info!(target : TRACING_STARTUP_TARGET, "Using {seconds}s max-age for {path:?}");
+            max_age.with_route(path, *seconds)
+        },
+    )
+}
+
+fn trailing_slash(router: Router, policy: &TrailingSlashPolicy) -> Router {
+    info!(target : TRACING_STARTUP_TARGET, "Using {policy:?} trailing slash policy");
+    match policy {
+        TrailingSlashPolicy::Strict => router,
+        TrailingSlashPolicy::Trim => trim_trailing_slash(router),
+        TrailingSlashPolicy::Redirect => redirect_trailing_slash(router),
+    }
+}
+
+fn backend_header(router: Router, expose: bool, storage_type: &StorageType) -> Router {
+    if !expose {
+        return router;
+    }
+    info!(target : TRACING_STARTUP_TARGET, "Exposing {:?} backend in responses", storage_type.name());
+
+    expose_data_source(router, storage_type.name())
+}
+
+fn https_policy(
+    router: Router,
+    policy: Option<HttpsPolicy>,
+    exempt: &[String],
+    public_url: &str,
+) -> Router {
+    let Some(policy) = policy else {
+        return router;
+    };
     info!(
         target : TRACING_STARTUP_TARGET,
-        "Tracing subscriber started with log level {:?} and {:?} log format", args.logging.log_level.to_string(), args.logging.log_format,
+        "Requiring HTTPS with {policy:?} policy, exempting {:?}",
+        exempt
     );
 
-    let bind_address = format!("{}:{}", args.runtime.bind_host, args.runtime.bind_port);
+    let enforcement = match policy {
+        HttpsPolicy::Redirect => HttpsEnforcement::Redirect,
+        HttpsPolicy::Reject => HttpsEnforcement::Reject,
+    };
+    require_https(router, enforcement, exempt, public_url)
+}
+
+fn chaos_testing(router: Router, chaos: &ChaosTesting) -> Router {
+    if !chaos.chaos_enable {
+        return router;
+    }
+    warn!(
+        target : TRACING_STARTUP_TARGET,
+        "Chaos testing enabled: {}ms delay and {} error rate",
+        chaos.chaos_delay_ms,
+        chaos.chaos_error_rate
+    );
+
+    inject_chaos(
+        router,
+        Chaos {
+            delay: Duration::from_millis(chaos.chaos_delay_ms),
+            error_rate: chaos.chaos_error_rate,
+        },
+    )
+}
+
+#[cfg(feature = "grpc")]
+fn spawn_grpc(host: IpAddr, port: Option<u16>, state: AppState) {
+    let Some(port) = port else {
+        return;
+    };
+    let address = SocketAddr::new(host, port);
+    info!(target : TRACING_STARTUP_TARGET, "Serving gRPC at {address:?}");
+
+    tokio::spawn(async move {
+        GrpcFacts::new(state)
+            .serve(address)
+            .await
+            .inspect_err(|err| {
+                error!(
+                    target : TRACING_STARTUP_TARGET,
+                    "Failed to start gRPC server: {err}"
+                );
+            })
+    });
+}
+
+fn init_tracing(logging: &Logging) -> Option<impl Sized> {
+    let log_layer = match logging.log_format {
+        LogFormat::Default => fmt::layer().boxed(),
+        LogFormat::Json => fmt::layer().json().boxed(),
+        LogFormat::Pretty => fmt::layer().pretty().boxed(),
+    };
+    let subscriber = tracing_subscriber::registry()
+        .with(logging.filter())
+        .with(log_layer);
+
+    #[cfg(feature = "sentry")]
+    {
+        let guard = error_reporting::init(logging.sentry_dsn.clone());
+        subscriber
+            .with(guard.as_ref().map(|_| error_reporting::layer()))
+            .init();
+        guard
+    }
+    #[cfg(not(feature = "sentry"))]
+    {
+        subscriber.init();
+        None::<()>
+    }
+}
+
+async fn bind_listener(runtime: &Runtime) -> TcpListener {
+    let bind_address = format!("{}:{}", runtime.bind_host, runtime.bind_port);
     let listener = TcpListener::bind(&bind_address)
         .await
         .inspect_err(|err| {
@@ -46,64 +367,120 @@ async fn main() {
         "Created listener at {bind_address:?}"
     );
 
-    let state = AppState {
-        facts: match args.storage.storage_type {
-            StorageType::Mocked => {
-                info!(target : TRACING_STARTUP_TARGET, "Using MockedRepository");
-                Arc::new(MockedFactsRepository {})
-            }
-            StorageType::Sqlx => {
-                info!(target : TRACING_STARTUP_TARGET, "Using SqlxRepository");
+    listener
+}
 
-                info!(target : TRACING_STARTUP_TARGET, "Creating pool for {:?}", &args.storage.storage_dsn);
-                let pool = PgPoolOptions::default()
-                    .connect(&args.storage.storage_dsn)
-                    .await
-                    .inspect_err(|err| {
-                        error!(
-                            target : TRACING_STARTUP_TARGET,
-                            "Cannot acquire pool: {err:?}"
-                        );
-                    })
-                    .unwrap();
+async fn run_self_test(storage: &Storage) -> ExitCode {
+    let (facts, _) = facts_repository(storage).await;
+    let report = self_test(facts.as_ref()).await;
+    println!("{}", serde_json::to_string(&report).unwrap());
 
-                Arc::new(SqlxFactsRepository::new(pool))
-            }
-        },
+    if report.passed() {
+        info!(target : TRACING_STARTUP_TARGET, "Self-test passed");
+        ExitCode::SUCCESS
+    } else {
+        error!(target : TRACING_STARTUP_TARGET, "Self-test failed");
+        ExitCode::FAILURE
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Config::parse();
+
+    let _error_reporting = init_tracing(&args.logging);
+    if args.runtime.self_test {
+        return run_self_test(&args.storage).await;
+    }
+
+    info!(
+        target : TRACING_STARTUP_TARGET,
+        "Tracing subscriber started with log level {:?} and {:?} log format", args.logging.log_level.to_string(), args.logging.log_format,
+    );
+
+    let listener = bind_listener(&args.runtime).await;
+
+    let (facts, changes) = facts_repository(&args.storage).await;
+    let auto_tags = auto_tag_rules(args.runtime.auto_tag_rules.as_deref()).await;
+    let profanity = profanity_filter(
+        args.runtime.profanity_words.as_deref(),
+        &args.runtime.profanity_mode,
+    )
+    .await;
+    let timeouts = route_timeouts(&args.runtime);
+    let max_age = route_cache_max_age(&args.runtime);
+    let state = AppState {
+        facts,
         auth_key: args.authentication.password_hash,
+        draining: Arc::default(),
+        public_url: args.runtime.public_url.clone(),
+        changes,
+        max_search_results: args.runtime.max_search_results,
+        min_body_length: args.runtime.min_body_length,
+        control_characters: match args.runtime.body_control_characters {
+            ControlCharactersPolicy::Reject => ControlCharacters::Reject,
+            ControlCharactersPolicy::Strip => ControlCharacters::Strip,
+        },
+        log_writes: args.logging.log_writes,
+        server_timing: args.runtime.server_timing,
+        expose_actors: args.runtime.expose_actors,
+        idempotent_delete: args.storage.idempotent_delete,
+        reject_duplicate_bodies: args.storage.reject_duplicate_bodies,
+        max_total_facts: args.storage.max_total_facts,
+        disable_random: args.runtime.disable_random,
+        auto_tags,
+        profanity,
+        endpoints: Endpoints {
+            create: args.endpoints.enable_create,
+            delete: args.endpoints.enable_delete,
+            search: args.endpoints.enable_search,
+        },
     };
 
+    #[cfg(feature = "grpc")]
+    spawn_grpc(
+        args.runtime.bind_host,
+        args.runtime.grpc_port,
+        state.clone(),
+    );
+
     let router = Router::new()
         .layer(TraceLayer::new_for_http())
-        .route(
-            "/",
-            get(|| async {
-                (
-                    axum::http::StatusCode::OK,
-                    Html(
-r#"
-<html>
-
-<h1>Facts</h1>
-<h2>Fact number 1: About smoking</h2>
-<p>
-  The phrase "smoking kills" is a direct statement about the severe health risks of tobacco use</br>
-  Smoking is a leading cause of preventable death globally, leading to cancer, heart disease, stroke, and lung diseases
-  like emphysema
-</p>
-
-</html>
-"#,
-                    ),
-                )
-            }),
-        )
+        .route("/", get(home))
         .nest("/api/facts", AppRouter::new(state.clone()).into())
+        .nest("/api/admin", AdminRouter::new(state.clone()).into());
+    #[cfg(feature = "graphql")]
+    let router = router.nest("/graphql", GraphQLRouter::new(state.clone()).into());
+    let router = router
+        .layer(from_fn_with_state(timeouts, timeout_middleware))
+        .layer(from_fn_with_state(max_age, cache_control_middleware))
         .with_state(state);
+
+    let router = trailing_slash(router, &args.runtime.trailing_slash);
+    let router = backend_header(
+        router,
+        args.runtime.expose_backend_header,
+        &args.storage.storage_type,
+    );
+    let router = https_policy(
+        router,
+        args.runtime.require_https,
+        &args.runtime.require_https_exempt,
+        &args.runtime.public_url,
+    );
+    let router = chaos_testing(router, &args.chaos);
     info!(target : TRACING_STARTUP_TARGET, "Created router");
 
     info!(target : TRACING_STARTUP_TARGET, "Starting server");
-    axum::serve(listener, router)
+    Server::new(listener, router)
+        .http2(args.runtime.http2)
+        .max_header_bytes(args.runtime.max_header_bytes)
+        .header_read_timeout(
+            args.runtime
+                .header_read_timeout_ms
+                .map(Duration::from_millis),
+        )
+        .serve()
         .await
         .inspect_err(|err| {
             error!(
@@ -112,4 +489,6 @@ r#"
             );
         })
         .unwrap();
+
+    ExitCode::SUCCESS
 }
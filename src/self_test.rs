@@ -0,0 +1,129 @@
+use std::{fmt::Display, future::Future, time::Instant};
+
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::facts::{CreateFactRequest, FactBody, FactTitle, FactsRepository};
+
+#[derive(Debug, Serialize)]
+pub struct SelfTestStep {
+    step: &'static str,
+    ok: bool,
+    elapsed_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SelfTestReport {
+    passed: bool,
+    steps: Vec<SelfTestStep>,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool {
+        self.passed
+    }
+
+    async fn run<T, E: Display>(
+        &mut self,
+        step: &'static str,
+        future: impl Future<Output = Result<T, E>>,
+    ) -> Option<T> {
+        let started = Instant::now();
+        let result = future.await;
+        let elapsed_ms = started.elapsed().as_millis();
+
+        self.passed &= result.is_ok();
+        self.steps.push(SelfTestStep {
+            step,
+            ok: result.is_ok(),
+            elapsed_ms,
+            error: result.as_ref().err().map(ToString::to_string),
+        });
+
+        result.ok()
+    }
+
+    fn fail(&mut self, step: &'static str, error: String) {
+        self.passed = false;
+        self.steps.push(SelfTestStep {
+            step,
+            ok: false,
+            elapsed_ms: 0,
+            error: Some(error),
+        });
+    }
+}
+
+pub async fn self_test(facts: &dyn FactsRepository) -> SelfTestReport {
+    let mut report = SelfTestReport {
+        passed: true,
+        steps: Vec::new(),
+    };
+    let marker = format!("self-test-{}", Utc::now().timestamp_micros());
+    let (Ok(title), Ok(body)) = (FactTitle::new(&marker), FactBody::new(&marker)) else {
+        report.fail("create", format!("Cannot build a fact from {marker:?}"));
+        return report;
+    };
+
+    let request = CreateFactRequest::new(&title, &body).with_public(false);
+    let Some(created) = report.run("create", facts.create(&request)).await else {
+        return report;
+    };
+
+    if let Some(fetched) = report.run("get", facts.get(created.id(), false)).await {
+        if String::from(fetched.body().to_owned()) != marker {
+            report.fail(
+                "compare",
+                "Stored body differs from the created one".to_owned(),
+            );
+        }
+    }
+    report.run("delete", facts.delete(created.id())).await;
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, time::Duration};
+
+    use fake::{Fake, Faker};
+
+    use super::*;
+    use crate::facts::{JsonFileFactsRepository, MockedFactsRepository};
+
+    #[tokio::test]
+    async fn passes_against_json_file() {
+        let path = temp_dir().join(format!("facts-{}.json", Faker.fake::<u64>()));
+        let repo = JsonFileFactsRepository::open(&path, Duration::from_secs(3600))
+            .await
+            .unwrap();
+
+        let report = self_test(&repo).await;
+
+        assert!(report.passed(), "{report:?}");
+        assert_eq!(
+            report
+                .steps
+                .iter()
+                .map(|step| step.step)
+                .collect::<Vec<_>>(),
+            ["create", "get", "delete"]
+        );
+        assert_eq!(repo.count(false).await.unwrap(), 0);
+
+        drop(repo);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(path.with_extension("json.lock"));
+    }
+
+    #[tokio::test]
+    async fn fails_when_stored_fact_differs() {
+        let report = self_test(&MockedFactsRepository {}).await;
+
+        assert!(!report.passed(), "{report:?}");
+        assert!(report.steps.iter().any(|step| !step.ok));
+    }
+}
@@ -0,0 +1,219 @@
+use std::{io, time::Duration};
+
+use axum::Router;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo, TokioTimer},
+    server::conn::auto::Builder,
+    service::TowerToHyperService,
+};
+use tokio::{net::TcpListener, time::sleep};
+use tracing::{debug, error};
+
+const TRACING_SERVER_TARGET: &str = "server";
+// Errors such as EMFILE persist until connections are closed, so retrying
+// straight away would spin the accept loop.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+pub struct Server {
+    listener: TcpListener,
+    router: Router,
+    http2: bool,
+    max_header_bytes: Option<u32>,
+    header_read_timeout: Option<Duration>,
+}
+
+impl Server {
+    pub fn new(listener: TcpListener, router: Router) -> Self {
+        Self {
+            listener,
+            router,
+            http2: false,
+            max_header_bytes: None,
+            header_read_timeout: None,
+        }
+    }
+
+    pub fn http2(mut self, enabled: bool) -> Self {
+        self.http2 = enabled;
+        self
+    }
+
+    pub fn max_header_bytes(mut self, max: Option<u32>) -> Self {
+        self.max_header_bytes = max;
+        self
+    }
+
+    pub fn header_read_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.header_read_timeout = timeout;
+        self
+    }
+
+    fn builder(&self) -> Builder<TokioExecutor> {
+        let mut builder = Builder::new(TokioExecutor::new());
+
+        if let Some(max) = self.max_header_bytes {
+            builder.http1().max_buf_size(max as usize);
+            builder.http2().max_header_list_size(max);
+        }
+
+        if let Some(timeout) = self.header_read_timeout {
+            builder
+                .http1()
+                .timer(TokioTimer::new())
+                .header_read_timeout(timeout);
+        }
+
+        if self.http2 {
+            builder
+        } else {
+            builder.http1_only()
+        }
+    }
+
+    pub async fn serve(self) -> io::Result<()> {
+        let builder = self.builder();
+
+        loop {
+            let (stream, remote_address) = match self.listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    error!(
+                        target : TRACING_SERVER_TARGET,
+                        "Cannot accept connection: {err:?}"
+                    );
+                    sleep(ACCEPT_RETRY_DELAY).await;
+                    continue;
+                }
+            };
+
+            let builder = builder.clone();
+            let service = TowerToHyperService::new(self.router.clone());
+
+            tokio::spawn(async move {
+                if let Err(err) = builder
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    debug!(
+                        target : TRACING_SERVER_TARGET,
+                        "Connection with {remote_address:?} closed: {err:?}"
+                    );
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use axum::http::{StatusCode, Version};
+    use reqwest::Client;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+        time::timeout,
+    };
+
+    use super::*;
+    use crate::facts::{AppRouter, AppState};
+
+    async fn spawn(
+        http2: bool,
+        max_header_bytes: Option<u32>,
+        header_read_timeout: Option<Duration>,
+    ) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let state = AppState::default();
+        let router = Router::new()
+            .nest("/api/facts", AppRouter::new(state.clone()).into())
+            .with_state(state);
+
+        tokio::spawn(
+            Server::new(listener, router)
+                .http2(http2)
+                .max_header_bytes(max_header_bytes)
+                .header_read_timeout(header_read_timeout)
+                .serve(),
+        );
+
+        address
+    }
+
+    #[tokio::test]
+    async fn http2_prior_knowledge() {
+        let address = spawn(true, None, None).await;
+        let client = Client::builder().http2_prior_knowledge().build().unwrap();
+
+        let response = client
+            .get(format!("http://{address}/api/facts/health"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.version(), Version::HTTP_2);
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn http1_by_default() {
+        let address = spawn(false, None, None).await;
+        let url = format!("http://{address}/api/facts/health");
+
+        let response = Client::new().get(&url).send().await.unwrap();
+
+        assert_eq!(response.version(), Version::HTTP_11);
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let client = Client::builder().http2_prior_knowledge().build().unwrap();
+
+        assert!(client.get(&url).send().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn oversized_headers() {
+        let address = spawn(false, Some(8192), None).await;
+        let url = format!("http://{address}/api/facts/health");
+
+        let response = Client::new()
+            .get(&url)
+            .header("x-padding", "a".repeat(4096))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = Client::new()
+            .get(&url)
+            .header("x-padding", "a".repeat(16384))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.status(),
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE
+        );
+    }
+
+    #[tokio::test]
+    async fn slow_headers() {
+        let address = spawn(false, None, Some(Duration::from_millis(100))).await;
+        let mut stream = TcpStream::connect(address).await.unwrap();
+
+        stream
+            .write_all(b"GET /api/facts/health HTTP/1.1\r\nHost: localhost\r\n")
+            .await
+            .unwrap();
+
+        let mut buffer = Vec::new();
+        let closed = timeout(Duration::from_secs(5), stream.read_to_end(&mut buffer)).await;
+
+        assert!(closed.is_ok());
+        assert!(!String::from_utf8_lossy(&buffer).contains("200 OK"));
+    }
+}
@@ -0,0 +1,108 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tokio::time::timeout;
+
+#[derive(Clone)]
+pub struct RouteTimeouts {
+    default: Duration,
+    overrides: Arc<HashMap<String, Duration>>,
+}
+
+impl RouteTimeouts {
+    pub fn new(default: Duration) -> Self {
+        Self {
+            default,
+            overrides: Arc::default(),
+        }
+    }
+
+    pub fn with_route(mut self, path: &str, duration: Duration) -> Self {
+        Arc::make_mut(&mut self.overrides).insert(path.to_owned(), duration);
+        self
+    }
+
+    fn get(&self, path: Option<&MatchedPath>) -> Duration {
+        path.and_then(|path| self.overrides.get(path.as_str()))
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+pub async fn timeout_middleware(
+    State(timeouts): State<RouteTimeouts>,
+    path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let duration = timeouts.get(path.as_ref());
+
+    match timeout(duration, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => (
+            StatusCode::GATEWAY_TIMEOUT,
+            format!("Request took longer than {duration:?}"),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, middleware::from_fn_with_state, routing::get, Router};
+    use tokio::time::sleep;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn slow() -> StatusCode {
+        sleep(Duration::from_millis(200)).await;
+        StatusCode::OK
+    }
+
+    fn router(timeouts: RouteTimeouts) -> Router {
+        Router::new()
+            .nest(
+                "/api",
+                Router::new()
+                    .route("/short", get(slow))
+                    .route("/long/{id}", get(slow)),
+            )
+            .layer(from_fn_with_state(timeouts, timeout_middleware))
+    }
+
+    async fn status(router: Router, uri: &str) -> StatusCode {
+        router
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn per_route_override() {
+        let router = router(
+            RouteTimeouts::new(Duration::from_millis(50))
+                .with_route("/api/long/{id}", Duration::from_secs(1)),
+        );
+
+        assert_eq!(
+            status(router.clone(), "/api/short").await,
+            StatusCode::GATEWAY_TIMEOUT
+        );
+        assert_eq!(status(router, "/api/long/1").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn default_timeout() {
+        let router = router(RouteTimeouts::new(Duration::from_secs(1)));
+
+        assert_eq!(status(router.clone(), "/api/short").await, StatusCode::OK);
+        assert_eq!(status(router, "/api/long/1").await, StatusCode::OK);
+    }
+}
@@ -0,0 +1,100 @@
+use axum::{
+    extract::Request,
+    http::{header::LOCATION, StatusCode},
+    middleware::{from_fn, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use tower_http::normalize_path::NormalizePath;
+
+pub fn trim_trailing_slash(router: Router) -> Router {
+    Router::new().fallback_service(NormalizePath::trim_trailing_slash(router))
+}
+
+pub fn redirect_trailing_slash(router: Router) -> Router {
+    Router::new()
+        .fallback_service(router)
+        .layer(from_fn(redirect_middleware))
+}
+
+async fn redirect_middleware(request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+
+    if path.len() > 1 && path.ends_with('/') {
+        let mut location = path.trim_end_matches('/').to_owned();
+        if location.is_empty() {
+            location.push('/');
+        }
+        if let Some(query) = request.uri().query() {
+            location = format!("{location}?{query}");
+        }
+
+        return (StatusCode::PERMANENT_REDIRECT, [(LOCATION, location)]).into_response();
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn router() -> Router {
+        Router::new().nest(
+            "/api",
+            Router::new().route("/random", get(|| async { StatusCode::OK })),
+        )
+    }
+
+    async fn call(router: Router, uri: &str) -> Response {
+        router
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn strict_by_default() {
+        assert_eq!(call(router(), "/api/random").await.status(), StatusCode::OK);
+        assert_eq!(
+            call(router(), "/api/random/").await.status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn trim() {
+        let router = trim_trailing_slash(router());
+
+        assert_eq!(
+            call(router.clone(), "/api/random").await.status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(router.clone(), "/api/random/").await.status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(router, "/api/missing/").await.status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn redirect() {
+        let router = redirect_trailing_slash(router());
+
+        assert_eq!(
+            call(router.clone(), "/api/random").await.status(),
+            StatusCode::OK
+        );
+
+        let response = call(router, "/api/random/?format=json").await;
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[LOCATION], "/api/random?format=json");
+    }
+}